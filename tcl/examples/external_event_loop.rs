@@ -0,0 +1,37 @@
+// Drives Tcl's event loop from a `mio::Poll` which also watches other event sources.
+// Requires Linux.
+
+#[cfg( target_os = "linux" )]
+fn main() -> tcl::TclResult<()> {
+    use mio::{Events, Interest, Poll, Token, unix::SourceFd};
+    use tcl::*;
+
+    tcl::notifier::install();
+
+    let interpreter = Interpreter::new()?;
+    interpreter.run( r#"
+        set ::ticks 0
+        proc tick {} {
+            puts "tick [incr ::ticks]"
+            if { $::ticks < 5 } { after 200 tick }
+        }
+        after 200 tick
+    "# )?;
+
+    let tcl_fd = tcl::notifier::as_raw_fd().unwrap();
+    let mut poll = Poll::new().unwrap();
+    poll.registry().register( &mut SourceFd( &tcl_fd ), Token(0), Interest::READABLE ).unwrap();
+    let mut events = Events::with_capacity( 16 );
+
+    tcl::notifier::process_events();
+    while let Some( timeout ) = tcl::notifier::timeout() {
+        poll.poll( &mut events, Some( timeout )).unwrap();
+        // Other event sources registered to `poll` would be handled here.
+        tcl::notifier::process_events();
+    }
+
+    Ok(())
+}
+
+#[cfg( not( target_os = "linux" ))]
+fn main() {}
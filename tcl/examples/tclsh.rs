@@ -0,0 +1,15 @@
+// A customized `tclsh` with Rust commands built in.
+//
+// cargo run --example tclsh -- script.tcl arg1 arg2
+
+use tcl::*;
+
+#[proc] fn mul( a: i32, b: i32 ) -> TclResult<i32> { Ok( a * b )}
+
+fn main() {
+    tcl::main( std::env::args(), |interp| {
+        unsafe{ interp.def_proc( "mul", mul ); } // it's safe for `#[proc] fn`.
+        interp.package_provide( "mul", "1.0" );
+        interp.run( "set tcl_rcFileName ~/.tclshrc" )
+    });
+}
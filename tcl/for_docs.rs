@@ -8,14 +8,113 @@ pub type ClientData = *mut ::std::os::raw::c_void;
 pub fn Tcl_Alloc(size: ::std::os::raw::c_uint) -> *mut ::std::os::raw::c_char {t!();}
 pub type Tcl_AppInitProc =
     ::std::option::Option<unsafe extern "C" fn(interp: *mut Tcl_Interp) -> ::std::os::raw::c_int>;
+pub const TCL_ALL_EVENTS: i32 = -3;
 pub const TCL_APPEND_VALUE: u32 = 4;
+pub fn Tcl_CallWhenDeleted(
+    interp: *mut Tcl_Interp,
+    proc_: Tcl_InterpDeleteProc,
+    clientData: ClientData,
+) {t!();}
+pub fn Tcl_CancelEval(
+    interp: *mut Tcl_Interp,
+    resultObjPtr: *mut Tcl_Obj,
+    clientData: ClientData,
+    flags: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub const TCL_CANCEL_UNWIND: u32 = 1048576;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Channel_ {
+    _unused: [u8; 0],
+}
+pub type Tcl_Channel = *mut Tcl_Channel_;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_ChannelType {
+    pub typeName: *const ::std::os::raw::c_char,
+    pub version: Tcl_ChannelTypeVersion,
+    pub closeProc: Tcl_DriverCloseProc,
+    pub inputProc: Tcl_DriverInputProc,
+    pub outputProc: Tcl_DriverOutputProc,
+    pub seekProc: Tcl_DriverSeekProc,
+    pub setOptionProc: Tcl_DriverSetOptionProc,
+    pub getOptionProc: Tcl_DriverGetOptionProc,
+    pub watchProc: Tcl_DriverWatchProc,
+    pub getHandleProc: Tcl_DriverGetHandleProc,
+    pub close2Proc: Tcl_DriverClose2Proc,
+    pub blockModeProc: Tcl_DriverBlockModeProc,
+    pub flushProc: Tcl_DriverFlushProc,
+    pub handlerProc: Tcl_DriverHandlerProc,
+    pub wideSeekProc: Tcl_DriverWideSeekProc,
+    pub threadActionProc: Tcl_DriverThreadActionProc,
+    pub truncateProc: Tcl_DriverTruncateProc,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_ChannelTypeVersion_ {
+    _unused: [u8; 0],
+}
+pub type Tcl_ChannelTypeVersion = *mut Tcl_ChannelTypeVersion_;
+pub fn Tcl_ChannelWatchProc(chanTypePtr: *const Tcl_ChannelType) -> Tcl_DriverWatchProc {t!();}
 pub type Tcl_CmdDeleteProc = ::std::option::Option<unsafe extern "C" fn(clientData: ClientData)>;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+pub struct Tcl_CmdInfo {
+    pub isNativeObjectProc: ::std::os::raw::c_int,
+    pub objProc: Tcl_ObjCmdProc,
+    pub objClientData: ClientData,
+    pub proc_: Tcl_CmdProc,
+    pub clientData: ClientData,
+    pub deleteProc: Tcl_CmdDeleteProc,
+    pub deleteData: ClientData,
+    pub namespacePtr: *mut Tcl_Namespace,
+}
+pub type Tcl_CmdObjTraceDeleteProc = ::std::option::Option<unsafe extern "C" fn(clientData: ClientData)>;
+pub type Tcl_CmdObjTraceProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        clientData: ClientData,
+        interp: *mut Tcl_Interp,
+        level: ::std::os::raw::c_int,
+        command: *const ::std::os::raw::c_char,
+        commandInfo: Tcl_Command,
+        objc: ::std::os::raw::c_int,
+        objv: *const *mut Tcl_Obj,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_CmdProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        clientData: ClientData,
+        interp: *mut Tcl_Interp,
+        argc: ::std::os::raw::c_int,
+        argv: *mut *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int,
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct Tcl_Command_ {
     _unused: [u8; 0],
 }
 pub type Tcl_Command = *mut Tcl_Command_;
+pub type Tcl_AlertNotifierProc = ::std::option::Option<unsafe extern "C" fn(clientData: ClientData)>;
+pub fn Tcl_CommandComplete(cmd: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int {t!();}
+pub const TCL_CONVERT_MULTIBYTE: i32 = -1;
+pub const TCL_CONVERT_NOSPACE: i32 = -4;
+pub const TCL_CONVERT_SYNTAX: i32 = -2;
+pub const TCL_CONVERT_UNKNOWN: i32 = -3;
+pub fn Tcl_CreateFileHandler(
+    fd: ::std::os::raw::c_int,
+    mask: ::std::os::raw::c_int,
+    proc_: Tcl_FileProc,
+    clientData: ClientData,
+) {t!();}
+pub type Tcl_CreateFileHandlerProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        fd: ::std::os::raw::c_int,
+        mask: ::std::os::raw::c_int,
+        proc_: Tcl_FileProc,
+        clientData: ClientData,
+    ),
+>;
 pub fn Tcl_CreateInterp() -> *mut Tcl_Interp {t!();}
 pub fn Tcl_CreateObjCommand(
     interp: *mut Tcl_Interp,
@@ -24,6 +123,14 @@ pub fn Tcl_CreateObjCommand(
     clientData: ClientData,
     deleteProc: Tcl_CmdDeleteProc,
 ) -> Tcl_Command {t!();}
+pub fn Tcl_CreateObjTrace(
+    interp: *mut Tcl_Interp,
+    level: ::std::os::raw::c_int,
+    flags: ::std::os::raw::c_int,
+    objProc: Tcl_CmdObjTraceProc,
+    clientData: ClientData,
+    delProc: Tcl_CmdObjTraceDeleteProc,
+) -> Tcl_Trace {t!();}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Tcl_DString {
@@ -40,6 +147,75 @@ pub fn Tcl_DStringAppendElement(
 pub fn Tcl_DStringStartSublist(dsPtr: *mut Tcl_DString) {t!();}
 pub fn Tcl_DStringEndSublist(dsPtr: *mut Tcl_DString) {t!();}
 pub fn Tcl_DStringFree(dsPtr: *mut Tcl_DString) {t!();}
+pub fn Tcl_DeleteFileHandler(fd: ::std::os::raw::c_int) {t!();}
+pub type Tcl_DeleteFileHandlerProc = ::std::option::Option<unsafe extern "C" fn(fd: ::std::os::raw::c_int)>;
+pub type Tcl_DriverBlockModeProc =
+    ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, mode: ::std::os::raw::c_int) -> ::std::os::raw::c_int>;
+pub type Tcl_DriverClose2Proc = ::std::option::Option<
+    unsafe extern "C" fn(instanceData: ClientData, interp: *mut Tcl_Interp, flags: ::std::os::raw::c_int) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverCloseProc =
+    ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, interp: *mut Tcl_Interp) -> ::std::os::raw::c_int>;
+pub type Tcl_DriverFlushProc = ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData) -> ::std::os::raw::c_int>;
+pub type Tcl_DriverGetHandleProc = ::std::option::Option<
+    unsafe extern "C" fn(instanceData: ClientData, direction: ::std::os::raw::c_int, handlePtr: *mut ClientData) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverGetOptionProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        interp: *mut Tcl_Interp,
+        optionName: *const ::std::os::raw::c_char,
+        dsPtr: *mut Tcl_DString,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverHandlerProc =
+    ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, interestMask: ::std::os::raw::c_int) -> ::std::os::raw::c_int>;
+pub type Tcl_DriverInputProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        buf: *mut ::std::os::raw::c_char,
+        toRead: ::std::os::raw::c_int,
+        errorCodePtr: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverOutputProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        buf: *const ::std::os::raw::c_char,
+        toWrite: ::std::os::raw::c_int,
+        errorCodePtr: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverSeekProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        offset: ::std::os::raw::c_long,
+        mode: ::std::os::raw::c_int,
+        errorCodePtr: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverSetOptionProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        interp: *mut Tcl_Interp,
+        optionName: *const ::std::os::raw::c_char,
+        value: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int,
+>;
+pub type Tcl_DriverThreadActionProc =
+    ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, action: ::std::os::raw::c_int)>;
+pub type Tcl_DriverTruncateProc =
+    ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, length: Tcl_WideInt) -> ::std::os::raw::c_int>;
+pub type Tcl_DriverWatchProc = ::std::option::Option<unsafe extern "C" fn(instanceData: ClientData, mask: ::std::os::raw::c_int)>;
+pub type Tcl_DriverWideSeekProc = ::std::option::Option<
+    unsafe extern "C" fn(
+        instanceData: ClientData,
+        offset: Tcl_WideInt,
+        mode: ::std::os::raw::c_int,
+        errorCodePtr: *mut ::std::os::raw::c_int,
+    ) -> Tcl_WideInt,
+>;
+pub fn Tcl_DeleteTrace(interp: *mut Tcl_Interp, trace: Tcl_Trace) {t!();}
 pub fn Tcl_DeleteInterp(interp: *mut Tcl_Interp) {t!();}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -73,16 +249,30 @@ pub fn Tcl_DictObjPut(
     keyPtr: *mut Tcl_Obj,
     valuePtr: *mut Tcl_Obj,
 ) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_DictObjPutKeyList(
+    interp: *mut Tcl_Interp,
+    dictPtr: *mut Tcl_Obj,
+    keyc: ::std::os::raw::c_int,
+    keyv: *const *mut Tcl_Obj,
+    valuePtr: *mut Tcl_Obj,
+) -> ::std::os::raw::c_int {t!();}
 pub fn Tcl_DictObjRemove(
     interp: *mut Tcl_Interp,
     dictPtr: *mut Tcl_Obj,
     keyPtr: *mut Tcl_Obj,
 ) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_DictObjRemoveKeyList(
+    interp: *mut Tcl_Interp,
+    dictPtr: *mut Tcl_Obj,
+    keyc: ::std::os::raw::c_int,
+    keyv: *const *mut Tcl_Obj,
+) -> ::std::os::raw::c_int {t!();}
 pub fn Tcl_DictObjSize(
     interp: *mut Tcl_Interp,
     dictPtr: *mut Tcl_Obj,
     sizePtr: *mut ::std::os::raw::c_int,
 ) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_DuplicateObj(objPtr: *mut Tcl_Obj) -> *mut Tcl_Obj {t!();}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Tcl_DictSearch {
@@ -90,10 +280,56 @@ pub struct Tcl_DictSearch {
     pub epoch: ::std::os::raw::c_int,
     pub dictionaryPtr: Tcl_Dict,
 }
+pub const TCL_DONT_WAIT: u32 = 2;
 pub fn Tcl_DoOneEvent(flags: ::std::os::raw::c_int) -> ::std::os::raw::c_int {t!();}
 pub type Tcl_DupInternalRepProc =
     ::std::option::Option<unsafe extern "C" fn(srcPtr: *mut Tcl_Obj, dupPtr: *mut Tcl_Obj)>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Event {
+    pub proc_: Tcl_EventProc,
+    pub nextPtr: *mut Tcl_Event,
+}
+pub type Tcl_EventProc = ::std::option::Option<
+    unsafe extern "C" fn(evPtr: *mut Tcl_Event, flags: ::std::os::raw::c_int) -> ::std::os::raw::c_int,
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Encoding_ {
+    _unused: [u8; 0],
+}
+pub type Tcl_Encoding = *mut Tcl_Encoding_;
+pub const TCL_ENCODING_END: u32 = 2;
+pub const TCL_ENCODING_START: u32 = 1;
+pub const TCL_ENCODING_STOPONERROR: u32 = 4;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_EncodingState_ {
+    _unused: [u8; 0],
+}
+pub type Tcl_EncodingState = *mut Tcl_EncodingState_;
 pub const TCL_ERROR: u32 = 1;
+pub const TCL_EXCEPTION: u32 = 8;
+pub const TCL_FILE_EVENTS: u32 = 8;
+pub type Tcl_FileProc =
+    ::std::option::Option<unsafe extern "C" fn(clientData: ClientData, mask: ::std::os::raw::c_int)>;
+pub fn Tcl_ExternalToUtf(
+    interp: *mut Tcl_Interp,
+    encoding: Tcl_Encoding,
+    src: *const ::std::os::raw::c_char,
+    srcLen: ::std::os::raw::c_int,
+    flags: ::std::os::raw::c_int,
+    statePtr: *mut Tcl_EncodingState,
+    dst: *mut ::std::os::raw::c_char,
+    dstLen: ::std::os::raw::c_int,
+    srcReadPtr: *mut ::std::os::raw::c_int,
+    dstWrotePtr: *mut ::std::os::raw::c_int,
+    dstCharsPtr: *mut ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_FreeEncoding(encoding: Tcl_Encoding) {t!();}
+pub fn Tcl_FreeParse(parsePtr: *mut Tcl_Parse) {t!();}
+pub type Tcl_FinalizeNotifierProc = ::std::option::Option<unsafe extern "C" fn(clientData: ClientData)>;
+pub const TCL_EVAL_GLOBAL: u32 = 131072;
 pub fn Tcl_EvalObjEx(
     interp: *mut Tcl_Interp,
     objPtr: *mut Tcl_Obj,
@@ -102,11 +338,30 @@ pub fn Tcl_EvalObjEx(
 pub fn Tcl_FindExecutable(argv0: *const ::std::os::raw::c_char) {t!();}
 pub type Tcl_FreeInternalRepProc =
     ::std::option::Option<unsafe extern "C" fn(objPtr: *mut Tcl_Obj)>;
+pub fn Tcl_GetAssocData(
+    interp: *mut Tcl_Interp,
+    name: *const ::std::os::raw::c_char,
+    procPtr: *mut Tcl_InterpDeleteProc,
+) -> ClientData {t!();}
+pub fn Tcl_GetCommandInfo(
+    interp: *mut Tcl_Interp,
+    cmdName: *const ::std::os::raw::c_char,
+    infoPtr: *mut Tcl_CmdInfo,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetCommandInfoFromToken(
+    token: Tcl_Command,
+    infoPtr: *mut Tcl_CmdInfo,
+) -> ::std::os::raw::c_int {t!();}
 pub fn Tcl_GetBooleanFromObj(
     interp: *mut Tcl_Interp,
     objPtr: *mut Tcl_Obj,
     intPtr: *mut ::std::os::raw::c_int,
 ) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetDouble(
+    interp: *mut Tcl_Interp,
+    src: *const ::std::os::raw::c_char,
+    doublePtr: *mut f64,
+) -> ::std::os::raw::c_int {t!();}
 pub fn Tcl_GetDoubleFromObj(
     interp: *mut Tcl_Interp,
     objPtr: *mut Tcl_Obj,
@@ -133,6 +388,29 @@ pub fn Tcl_GetStringFromObj(
     objPtr: *mut Tcl_Obj,
     lengthPtr: *mut ::std::os::raw::c_int,
 ) -> *mut ::std::os::raw::c_char {t!();}
+pub fn Tcl_GetByteArrayFromObj(objPtr: *mut Tcl_Obj, lengthPtr: *mut ::std::os::raw::c_int) -> *mut ::std::os::raw::c_uchar {t!();}
+pub fn Tcl_GetChannel(
+    interp: *mut Tcl_Interp,
+    chanName: *const ::std::os::raw::c_char,
+    modePtr: *mut ::std::os::raw::c_int,
+) -> Tcl_Channel {t!();}
+pub fn Tcl_GetChannelHandle(chan: Tcl_Channel, direction: ::std::os::raw::c_int, handlePtr: *mut ClientData) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetChannelInstanceData(chan: Tcl_Channel) -> ClientData {t!();}
+pub fn Tcl_GetChannelMode(chan: Tcl_Channel) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetChannelType(chan: Tcl_Channel) -> *const Tcl_ChannelType {t!();}
+pub fn Tcl_GetCharLength(objPtr: *mut Tcl_Obj) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetErrno() -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_GetEncoding(interp: *mut Tcl_Interp, name: *const ::std::os::raw::c_char) -> Tcl_Encoding {t!();}
+pub fn Tcl_GetEncodingName(encoding: Tcl_Encoding) -> *const ::std::os::raw::c_char {t!();}
+pub fn Tcl_GetEncodingNames(interp: *mut Tcl_Interp) {t!();}
+pub fn Tcl_GetRegExpFromObj(
+    interp: *mut Tcl_Interp,
+    patObj: *mut Tcl_Obj,
+    flags: ::std::os::raw::c_int,
+) -> Tcl_RegExp {t!();}
+pub fn Tcl_GetStackedChannel(chan: Tcl_Channel) -> Tcl_Channel {t!();}
+pub fn Tcl_GetTopChannel(chan: Tcl_Channel) -> Tcl_Channel {t!();}
+pub fn Tcl_GetTime(timeBuf: *mut Tcl_Time) {t!();}
 pub fn Tcl_GetWideIntFromObj(
     interp: *mut Tcl_Interp,
     objPtr: *mut Tcl_Obj,
@@ -147,8 +425,20 @@ pub struct Tcl_Interp {
         ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_char)>,
     pub errorLineDontUse: ::std::os::raw::c_int,
 }
+pub type Tcl_InterpDeleteProc =
+    ::std::option::Option<unsafe extern "C" fn(clientData: ClientData, interp: *mut Tcl_Interp)>;
 pub fn Tcl_InvalidateStringRep(objPtr: *mut Tcl_Obj) {t!();}
+pub type Tcl_InitNotifierProc = ::std::option::Option<unsafe extern "C" fn() -> ClientData>;
 pub const TCL_LEAVE_ERR_MSG: u32 = 512;
+pub fn Tcl_LimitGetTime(interp: *mut Tcl_Interp, timeLimitPtr: *mut Tcl_Time) {t!();}
+pub fn Tcl_LimitSetTime(interp: *mut Tcl_Interp, timeLimitPtr: *mut Tcl_Time) {t!();}
+pub fn Tcl_LimitTypeEnabled(
+    interp: *mut Tcl_Interp,
+    type_: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_LimitTypeReset(interp: *mut Tcl_Interp, type_: ::std::os::raw::c_int) {t!();}
+pub fn Tcl_LimitTypeSet(interp: *mut Tcl_Interp, type_: ::std::os::raw::c_int) {t!();}
+pub const TCL_LIMIT_TIME: u32 = 2;
 pub const TCL_LIST_ELEMENT: u32 = 8;
 pub fn Tcl_ListObjAppendElement(
     interp: *mut Tcl_Interp,
@@ -185,6 +475,16 @@ pub fn Tcl_ListObjReplace(
     objc: ::std::os::raw::c_int,
     objv: *const *mut Tcl_Obj,
 ) -> ::std::os::raw::c_int {t!();}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Namespace {
+    pub name: *mut ::std::os::raw::c_char,
+    pub fullName: *mut ::std::os::raw::c_char,
+    pub clientData: ClientData,
+    pub deleteProc: Tcl_NamespaceDeleteProc,
+    pub parentPtr: *mut Tcl_Namespace,
+}
+pub type Tcl_NamespaceDeleteProc = ::std::option::Option<unsafe extern "C" fn(clientData: ClientData)>;
 pub fn Tcl_NewDictObj() -> *mut Tcl_Obj {t!();}
 pub fn Tcl_NewDoubleObj(doubleValue: f64) -> *mut Tcl_Obj {t!();}
 pub fn Tcl_NewIntObj(intValue: ::std::os::raw::c_int) -> *mut Tcl_Obj {t!();}
@@ -226,6 +526,7 @@ pub struct Tcl_Obj__bindgen_ty_1__bindgen_ty_2 {
     pub ptr: *mut ::std::os::raw::c_void,
     pub value: ::std::os::raw::c_ulong,
 }
+pub fn Tcl_NewByteArrayObj(bytes: *const ::std::os::raw::c_uchar, length: ::std::os::raw::c_int) -> *mut Tcl_Obj {t!();}
 pub fn Tcl_ObjGetVar2(
     interp: *mut Tcl_Interp,
     part1Ptr: *mut Tcl_Obj,
@@ -248,7 +549,58 @@ pub struct Tcl_ObjType {
     pub updateStringProc: Tcl_UpdateStringProc,
     pub setFromAnyProc: Tcl_SetFromAnyProc,
 }
+pub fn Tcl_MainEx(
+    argc: ::std::os::raw::c_int,
+    argv: *mut *mut ::std::os::raw::c_char,
+    appInitProc: Tcl_AppInitProc,
+    interp: *mut Tcl_Interp,
+) {t!();}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_NotifierProcs {
+    pub setTimerProc: Tcl_SetTimerProc,
+    pub waitForEventProc: Tcl_WaitForEventProc,
+    pub createFileHandlerProc: Tcl_CreateFileHandlerProc,
+    pub deleteFileHandlerProc: Tcl_DeleteFileHandlerProc,
+    pub initNotifierProc: Tcl_InitNotifierProc,
+    pub finalizeNotifierProc: Tcl_FinalizeNotifierProc,
+    pub alertNotifierProc: Tcl_AlertNotifierProc,
+    pub serviceModeHookProc: Tcl_ServiceModeHookProc,
+}
 pub const TCL_OK: u32 = 0;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Parse {
+    pub commentStart: *const ::std::os::raw::c_char,
+    pub commentSize: ::std::os::raw::c_int,
+    pub commandStart: *const ::std::os::raw::c_char,
+    pub commandSize: ::std::os::raw::c_int,
+    pub numWords: ::std::os::raw::c_int,
+    pub tokenPtr: *mut Tcl_Token,
+    pub numTokens: ::std::os::raw::c_int,
+    pub tokensAvailable: ::std::os::raw::c_int,
+    pub errorType: ::std::os::raw::c_int,
+    pub string: *const ::std::os::raw::c_char,
+    pub end: *const ::std::os::raw::c_char,
+    pub interp: *mut Tcl_Interp,
+    pub term: *const ::std::os::raw::c_char,
+    pub incomplete: ::std::os::raw::c_int,
+    pub staticTokens: [Tcl_Token; 20usize],
+}
+pub fn Tcl_ParseCommand(
+    interp: *mut Tcl_Interp,
+    start: *const ::std::os::raw::c_char,
+    numBytes: ::std::os::raw::c_int,
+    nested: ::std::os::raw::c_int,
+    parsePtr: *mut Tcl_Parse,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_ParseVarName(
+    interp: *mut Tcl_Interp,
+    start: *const ::std::os::raw::c_char,
+    numBytes: ::std::os::raw::c_int,
+    parsePtr: *mut Tcl_Parse,
+    append: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
 pub fn Tcl_PkgProvide(
     interp: *mut Tcl_Interp,
     name: *const ::std::os::raw::c_char,
@@ -259,12 +611,78 @@ pub fn Tcl_RegisterObjType(typePtr: *const Tcl_ObjType) {t!();}
 pub type Tcl_SetFromAnyProc = ::std::option::Option<
     unsafe extern "C" fn(interp: *mut Tcl_Interp, objPtr: *mut Tcl_Obj) -> ::std::os::raw::c_int,
 >;
+pub fn Tcl_QueueEvent(evPtr: *mut Tcl_Event, position: Tcl_QueuePosition) {t!();}
+pub const Tcl_QueuePosition_TCL_QUEUE_TAIL: Tcl_QueuePosition = 0;
+pub const Tcl_QueuePosition_TCL_QUEUE_HEAD: Tcl_QueuePosition = 1;
+pub const Tcl_QueuePosition_TCL_QUEUE_MARK: Tcl_QueuePosition = 2;
+pub type Tcl_QueuePosition = ::std::os::raw::c_uint;
+pub const TCL_READABLE: u32 = 2;
+pub fn Tcl_ReadRaw(chan: Tcl_Channel, dst: *mut ::std::os::raw::c_char, bytesToRead: ::std::os::raw::c_int) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_RecordAndEvalObj(
+    interp: *mut Tcl_Interp,
+    cmdPtr: *mut Tcl_Obj,
+    flags: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub type Tcl_ServiceModeHookProc = ::std::option::Option<unsafe extern "C" fn(mode: ::std::os::raw::c_int)>;
+pub const TCL_REG_ADVANCED: u32 = 3;
+pub const TCL_REG_EXPANDED: u32 = 32;
+pub const TCL_REG_NLANCH: u32 = 128;
+pub const TCL_REG_NLSTOP: u32 = 64;
+pub const TCL_REG_NEWLINE: u32 = 192;
+pub const TCL_REG_NOCASE: u32 = 8;
+pub const TCL_REG_NOTBOL: u32 = 1;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_RegExp_ {
+    _unused: [u8; 0],
+}
+pub type Tcl_RegExp = *mut Tcl_RegExp_;
+pub fn Tcl_RegExpExecObj(
+    interp: *mut Tcl_Interp,
+    regexp: Tcl_RegExp,
+    textObj: *mut Tcl_Obj,
+    offset: ::std::os::raw::c_int,
+    nmatches: ::std::os::raw::c_int,
+    flags: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_RegExpGetInfo(regexp: Tcl_RegExp, infoPtr: *mut Tcl_RegExpInfo) {t!();}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_RegExpIndices {
+    pub start: ::std::os::raw::c_long,
+    pub end: ::std::os::raw::c_long,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_RegExpInfo {
+    pub nsubs: ::std::os::raw::c_int,
+    pub matches: *mut Tcl_RegExpIndices,
+    pub extendStart: ::std::os::raw::c_long,
+    pub reserved: ::std::os::raw::c_long,
+}
+pub fn Tcl_SetAssocData(
+    interp: *mut Tcl_Interp,
+    name: *const ::std::os::raw::c_char,
+    proc_: Tcl_InterpDeleteProc,
+    clientData: ClientData,
+) {t!();}
+pub fn Tcl_SetErrno(err: ::std::os::raw::c_int) {t!();}
 pub fn Tcl_SetListObj(
     objPtr: *mut Tcl_Obj,
     objc: ::std::os::raw::c_int,
     objv: *const *mut Tcl_Obj,
 ) {t!();}
+pub fn Tcl_SetNotifier(notifierProcPtr: *mut Tcl_NotifierProcs) {t!();}
 pub fn Tcl_SetObjErrorCode(interp: *mut Tcl_Interp, errorObjPtr: *mut Tcl_Obj) {t!();}
+pub fn Tcl_StringMatch(str: *const ::std::os::raw::c_char, pattern: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_StackChannel(
+    interp: *mut Tcl_Interp,
+    typePtr: *const Tcl_ChannelType,
+    instanceData: ClientData,
+    mask: ::std::os::raw::c_int,
+    prevChan: Tcl_Channel,
+) -> Tcl_Channel {t!();}
+pub type Tcl_SetTimerProc = ::std::option::Option<unsafe extern "C" fn(timePtr: *const Tcl_Time)>;
 pub fn Tcl_SetObjResult(interp: *mut Tcl_Interp, resultObjPtr: *mut Tcl_Obj) {t!();}
 pub type Tcl_ObjCmdProc = ::std::option::Option<
     unsafe extern "C" fn(
@@ -274,6 +692,23 @@ pub type Tcl_ObjCmdProc = ::std::option::Option<
         objv: *const *mut Tcl_Obj,
     ) -> ::std::os::raw::c_int,
 >;
+pub const TCL_TOKEN_WORD: u32 = 1;
+pub const TCL_TOKEN_SIMPLE_WORD: u32 = 2;
+pub const TCL_TOKEN_TEXT: u32 = 4;
+pub const TCL_TOKEN_BS: u32 = 8;
+pub const TCL_TOKEN_COMMAND: u32 = 16;
+pub const TCL_TOKEN_VARIABLE: u32 = 32;
+pub const TCL_TOKEN_SUB_EXPR: u32 = 64;
+pub const TCL_TOKEN_OPERATOR: u32 = 128;
+pub const TCL_TOKEN_EXPAND_WORD: u32 = 256;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Token {
+    pub type_: ::std::os::raw::c_int,
+    pub start: *const ::std::os::raw::c_char,
+    pub size: ::std::os::raw::c_int,
+    pub numComponents: ::std::os::raw::c_int,
+}
 pub fn Tcl_UnsetVar(
     interp: *mut Tcl_Interp,
     varName: *const ::std::os::raw::c_char,
@@ -285,6 +720,19 @@ pub fn Tcl_UnsetVar2(
     part2: *const ::std::os::raw::c_char,
     flags: ::std::os::raw::c_int,
 ) -> ::std::os::raw::c_int {t!();}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Time {
+    pub sec: ::std::os::raw::c_long,
+    pub usec: ::std::os::raw::c_long,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tcl_Trace_ {
+    _unused: [u8; 0],
+}
+pub fn Tcl_UnstackChannel(interp: *mut Tcl_Interp, chan: Tcl_Channel) -> ::std::os::raw::c_int {t!();}
+pub type Tcl_Trace = *mut Tcl_Trace_;
 pub type Tcl_UpdateStringProc = ::std::option::Option<unsafe extern "C" fn(objPtr: *mut Tcl_Obj)>;
 pub type Tcl_WideInt = ::std::os::raw::c_longlong;
 pub fn Tcl_WrongNumArgs(
@@ -294,3 +742,72 @@ pub fn Tcl_WrongNumArgs(
     message: *const ::std::os::raw::c_char,
 ) {t!();}
 pub fn TclFreeObj(objPtr: *mut Tcl_Obj) {t!();}
+pub fn Tcl_UtfToExternal(
+    interp: *mut Tcl_Interp,
+    encoding: Tcl_Encoding,
+    src: *const ::std::os::raw::c_char,
+    srcLen: ::std::os::raw::c_int,
+    flags: ::std::os::raw::c_int,
+    statePtr: *mut Tcl_EncodingState,
+    dst: *mut ::std::os::raw::c_char,
+    dstLen: ::std::os::raw::c_int,
+    srcReadPtr: *mut ::std::os::raw::c_int,
+    dstWrotePtr: *mut ::std::os::raw::c_int,
+    dstCharsPtr: *mut ::std::os::raw::c_int,
+) -> ::std::os::raw::c_int {t!();}
+pub type Tcl_WaitForEventProc =
+    ::std::option::Option<unsafe extern "C" fn(timePtr: *const Tcl_Time) -> ::std::os::raw::c_int>;
+pub const TCL_WRITABLE: u32 = 4;
+pub fn Tcl_ZlibAdler32(
+    initValue: ::std::os::raw::c_uint,
+    bytes: *const ::std::os::raw::c_uchar,
+    length: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_uint {t!();}
+pub const TCL_ZLIB_COMPRESS_BEST: u32 = 9;
+pub const TCL_ZLIB_COMPRESS_DEFAULT: i32 = -1;
+pub const TCL_ZLIB_COMPRESS_FAST: u32 = 1;
+pub const TCL_ZLIB_COMPRESS_NONE: u32 = 0;
+pub fn Tcl_ZlibCRC32(
+    initValue: ::std::os::raw::c_uint,
+    bytes: *const ::std::os::raw::c_uchar,
+    length: ::std::os::raw::c_int,
+) -> ::std::os::raw::c_uint {t!();}
+pub fn Tcl_ZlibDeflate(
+    interp: *mut Tcl_Interp,
+    format: ::std::os::raw::c_int,
+    data: *mut Tcl_Obj,
+    level: ::std::os::raw::c_int,
+    gzipHeaderDictObj: *mut Tcl_Obj,
+) -> ::std::os::raw::c_int {t!();}
+pub const TCL_ZLIB_FINALIZE: u32 = 4;
+pub const TCL_ZLIB_FLUSH: u32 = 2;
+pub const TCL_ZLIB_FORMAT_AUTO: u32 = 8;
+pub const TCL_ZLIB_FORMAT_GZIP: u32 = 4;
+pub const TCL_ZLIB_FORMAT_RAW: u32 = 1;
+pub const TCL_ZLIB_FORMAT_ZLIB: u32 = 2;
+pub const TCL_ZLIB_FULLFLUSH: u32 = 3;
+pub fn Tcl_ZlibInflate(
+    interp: *mut Tcl_Interp,
+    format: ::std::os::raw::c_int,
+    data: *mut Tcl_Obj,
+    buffersize: ::std::os::raw::c_int,
+    gzipHeaderDictObj: *mut Tcl_Obj,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_WriteRaw(chan: Tcl_Channel, src: *const ::std::os::raw::c_char, srcLen: ::std::os::raw::c_int) -> ::std::os::raw::c_int {t!();}
+pub const TCL_ZLIB_NO_FLUSH: u32 = 0;
+pub type Tcl_ZlibStream = *mut ::std::os::raw::c_void;
+pub fn Tcl_ZlibStreamChecksum(zshandle: Tcl_ZlibStream) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_ZlibStreamClose(zshandle: Tcl_ZlibStream) -> ::std::os::raw::c_int {t!();}
+pub const TCL_ZLIB_STREAM_DEFLATE: u32 = 16;
+pub fn Tcl_ZlibStreamEof(zshandle: Tcl_ZlibStream) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_ZlibStreamGet(zshandle: Tcl_ZlibStream, data: *mut Tcl_Obj, count: ::std::os::raw::c_int) -> ::std::os::raw::c_int {t!();}
+pub const TCL_ZLIB_STREAM_INFLATE: u32 = 32;
+pub fn Tcl_ZlibStreamInit(
+    interp: *mut Tcl_Interp,
+    mode: ::std::os::raw::c_int,
+    format: ::std::os::raw::c_int,
+    level: ::std::os::raw::c_int,
+    dictObj: *mut Tcl_Obj,
+    zshandlePtr: *mut Tcl_ZlibStream,
+) -> ::std::os::raw::c_int {t!();}
+pub fn Tcl_ZlibStreamPut(zshandle: Tcl_ZlibStream, data: *mut Tcl_Obj, flush: ::std::os::raw::c_int) -> ::std::os::raw::c_int {t!();}
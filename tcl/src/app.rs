@@ -0,0 +1,92 @@
+//! Entry point for `tclsh`-like programs.
+
+use crate::{
+    Obj,
+    interp::Interp,
+};
+
+use std::{
+    cell::RefCell,
+    ffi::CString,
+    fmt::Display,
+    mem,
+    os::raw::c_int,
+};
+
+type AppInit = Box<dyn FnOnce( &Interp ) -> Result<(), String>>;
+
+thread_local! {
+    static APP_INIT: RefCell<Option<AppInit>> = const{ RefCell::new( None )};
+}
+
+/// Main program for Tcl-based applications, i.e. a customized `tclsh`. It never returns.
+///
+/// `args` are the command line arguments, starting with the program name. As `tclsh` does,
+/// the argument following the program name is taken as the script file to run if it does not
+/// start with `-`, as is the file in `-encoding name file`. The remaining arguments go to
+/// `argv`. Without a script file, the program runs interactively. The variables `argc`, `argv`, `argv0` and `tcl_interactive`
+/// are set before calling `init`, which may modify them, e.g. to force interactive mode.
+///
+/// `init` is called with the interpreter after `Tcl_Init()`, to register commands and
+/// packages, or to set `tcl_rcFileName`. If it fails, the error is reported but the
+/// program keeps running, which is the behavior of `Tcl_Main()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tcl::*;
+///
+/// #[proc] fn mul( a: i32, b: i32 ) -> TclResult<i32> { Ok( a * b )}
+///
+/// tcl::main( std::env::args(), |interp| {
+///     unsafe{ interp.def_proc( "mul", mul ); } // it's safe for `#[proc] fn`.
+///     interp.package_provide( "mul", "1.0" );
+///     interp.run( "set tcl_rcFileName ~/.mytclshrc" )
+/// });
+/// ```
+pub fn main<E: Display>(
+    args : impl Iterator<Item=String>,
+    init : impl FnOnce( &Interp ) -> Result<(), E> + 'static,
+) -> ! {
+    crate::init();
+
+    APP_INIT.with( |app_init| {
+        *app_init.borrow_mut() = Some( Box::new( move |interp: &Interp| init( interp ).map_err( |err| err.to_string() )));
+    });
+
+    let mut v: Vec<_> = args
+        .map( |arg| CString::new( Vec::<u8>::from( arg ))
+            .expect( "String should not contain nul character" )
+            .into_raw() )
+        .collect();
+    let argc = v.len() as c_int;
+    let argv = v.as_mut_ptr();
+    mem::forget( v );
+
+    unsafe {
+        let interp = clib::Tcl_CreateInterp();
+        clib::Tcl_MainEx( argc, argv, Some( tcl_app_init_proc ), interp );
+    }
+
+    // `Tcl_MainEx()` exits the process on its own.
+    std::process::exit( 0 )
+}
+
+unsafe extern "C" fn tcl_app_init_proc( interp: *mut clib::Tcl_Interp ) -> c_int {
+    const TCL_OK: c_int = clib::TCL_OK as c_int;
+
+    let result = clib::Tcl_Init( interp );
+    if result != TCL_OK {
+        return result;
+    }
+
+    let init = APP_INIT.with( |app_init| app_init.borrow_mut().take() );
+    if let (Some( init ), Ok( interp )) = ( init, Interp::from_raw( interp )) {
+        if let Err( message ) = init( &interp ) {
+            clib::Tcl_SetObjResult( interp.as_ptr(), Obj::from( message ).into_raw() );
+            return clib::TCL_ERROR as c_int;
+        }
+    }
+
+    TCL_OK
+}
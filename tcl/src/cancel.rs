@@ -0,0 +1,202 @@
+//! Cancellation and time limits of running scripts.
+
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
+use crate::{
+    Obj,
+    error::{
+        Cancelled,
+        InterpError,
+        LimitExceeded,
+    },
+    interp::Interp,
+};
+
+use std::{
+    os::raw::{c_char, c_int, c_long},
+    ptr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A handle to cancel the script being evaluated in an interpreter, from any thread.
+///
+/// The token becomes a no-op after the interpreter has been deleted.
+#[derive( Clone, Debug )]
+pub struct CancelToken( Arc<Mutex<Option<usize>>> );
+
+impl CancelToken {
+    fn cancel_with_flags( &self, flags: c_int ) -> bool {
+        match self.0.lock() {
+            Ok( interp ) => match *interp {
+                Some( interp ) => unsafe {
+                    clib::Tcl_CancelEval( interp as *mut clib::Tcl_Interp, ptr::null_mut(), ptr::null_mut(), flags )
+                        == clib::TCL_OK as c_int
+                },
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Cancels the script being evaluated. The cancellation can be caught by `catch` in
+    /// the script. Returns false if the interpreter has been deleted.
+    pub fn cancel( &self ) -> bool {
+        self.cancel_with_flags( 0 )
+    }
+
+    /// Cancels the script being evaluated, unwinding the whole call stack without giving
+    /// `catch` in the script any chance to intercept. Returns false if the interpreter has
+    /// been deleted.
+    pub fn cancel_unwind( &self ) -> bool {
+        self.cancel_with_flags( clib::TCL_CANCEL_UNWIND as c_int )
+    }
+}
+
+// The key of the assoc data holding the token shared by all `CancelToken`s of an interpreter.
+const TOKEN_KEY: &[u8] = b"tcl_rs_cancel_token\0";
+
+unsafe extern "C" fn forget_interp( client_data: clib::ClientData, _interp: *mut clib::Tcl_Interp ) {
+    let token = Arc::from_raw( client_data as *const Mutex<Option<usize>> );
+    if let Ok( mut interp ) = token.lock() {
+        *interp = None;
+    };
+}
+
+impl Interp {
+    /// Returns a token which can be sent to other threads to cancel the script being
+    /// evaluated in this interpreter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    ///
+    /// let interpreter = Interpreter::new().unwrap();
+    /// let token = interpreter.cancel_token();
+    ///
+    /// std::thread::spawn( move || {
+    ///     std::thread::sleep( std::time::Duration::from_millis( 100 ));
+    ///     token.cancel();
+    /// });
+    ///
+    /// let err = interpreter.eval( "while 1 {}" ).unwrap_err();
+    /// assert!( err.is_cancelled() );
+    /// ```
+    pub fn cancel_token( &self ) -> CancelToken {
+        let key = TOKEN_KEY.as_ptr() as *const c_char;
+        unsafe {
+            let token = clib::Tcl_GetAssocData( self.as_ptr(), key, ptr::null_mut() ) as *const Mutex<Option<usize>>;
+            if token.is_null() {
+                let interp = Arc::new( Mutex::new( Some( self.as_ptr() as usize )));
+                clib::Tcl_SetAssocData(
+                    self.as_ptr(),
+                    key,
+                    Some( forget_interp ),
+                    Arc::into_raw( interp.clone() ) as clib::ClientData,
+                );
+                CancelToken( interp )
+            } else {
+                Arc::increment_strong_count( token );
+                CancelToken( Arc::from_raw( token ))
+            }
+        }
+    }
+
+    /// Evaluates `code` like `Interp::eval()`, but distinguishes cancellation by
+    /// `CancelToken` from other errors.
+    #[cex]
+    pub fn eval_cancellable( &self, code: impl Into<Obj> ) -> Result!( Obj throws Cancelled, InterpError ) {
+        match self.eval( code ) {
+            Ok( obj ) => Ok( obj ),
+            Err( err ) => if err.is_cancelled() {
+                throw!( Cancelled( err ))
+            } else {
+                throw!( err )
+            },
+        }
+    }
+
+    /// Evaluates `code` like `Interp::eval()`, with a time limit of `timeout`. The script
+    /// is aborted with `LimitExceeded` when running out of time.
+    ///
+    /// Note that Tcl checks the time limit periodically between commands, so a single
+    /// long-running command will not be interrupted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use tcl::*;
+    ///
+    /// let interpreter = Interpreter::new().unwrap();
+    /// let err = interpreter.eval_with_timeout( "while 1 {}", Duration::from_millis( 100 )).unwrap_err();
+    /// assert!( matches!( TclError::from( err ), TclError::LimitExceeded(_) ));
+    ///
+    /// let result = interpreter.eval_with_timeout( "expr {6*7}", Duration::from_secs( 1 ));
+    /// assert_eq!( result.unwrap().as_i32(), 42 );
+    /// ```
+    #[cex]
+    pub fn eval_with_timeout( &self, code: impl Into<Obj>, timeout: Duration )
+        -> Result!( Obj throws Cancelled, InterpError, LimitExceeded )
+    {
+        let limit_time = clib::TCL_LIMIT_TIME as c_int;
+        let interp = self.as_ptr();
+
+        let mut deadline = clib::Tcl_Time{ sec: 0, usec: 0 };
+        let mut saved_deadline = clib::Tcl_Time{ sec: 0, usec: 0 };
+
+        let was_enabled = unsafe {
+            clib::Tcl_GetTime( &mut deadline );
+            clib::Tcl_LimitGetTime( interp, &mut saved_deadline );
+            clib::Tcl_LimitTypeEnabled( interp, limit_time ) != 0
+        };
+
+        // A timeout too long to represent is as good as none.
+        let usec = deadline.usec as u128 + timeout.as_micros();
+        let sec = c_long::try_from( usec / 1_000_000 ).unwrap_or( c_long::MAX );
+        deadline.sec = deadline.sec.saturating_add( sec );
+        deadline.usec = ( usec % 1_000_000 ) as c_long;
+
+        unsafe {
+            clib::Tcl_LimitSetTime( interp, &mut deadline );
+            clib::Tcl_LimitTypeSet( interp, limit_time );
+        }
+
+        let result = self.eval( code );
+
+        unsafe {
+            clib::Tcl_LimitTypeReset( interp, limit_time );
+            clib::Tcl_LimitSetTime( interp, &mut saved_deadline );
+            if was_enabled {
+                clib::Tcl_LimitTypeSet( interp, limit_time );
+            }
+        }
+
+        match result {
+            Ok( obj ) => Ok( obj ),
+            Err( err ) => if err.is_limit_exceeded() {
+                throw!( LimitExceeded( err ))
+            } else if err.is_cancelled() {
+                throw!( Cancelled( err ))
+            } else {
+                throw!( err )
+            },
+        }
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn limit_is_restored() {
+        let interpreter = Interpreter::new().unwrap();
+        assert!( interpreter.eval_with_timeout( "while 1 {}", Duration::from_millis( 50 )).is_err() );
+        assert!( interpreter.eval( "after 100; expr 1" ).is_ok() );
+    }
+}
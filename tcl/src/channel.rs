@@ -0,0 +1,254 @@
+//! Rust transforms stacked onto Tcl channels, as `chan push` does with Tcl procs.
+//!
+//! Once a `Transform` is pushed onto a channel, every byte a Tcl script reads from or
+//! writes to that channel passes through it, until it is popped or the channel is closed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use tcl::*;
+//! use tcl::channel::Transform;
+//!
+//! struct Rot13;
+//!
+//! fn rot13( byte: u8 ) -> u8 {
+//!     match byte {
+//!         b'a'..=b'z' => ( byte - b'a' + 13 ) % 26 + b'a',
+//!         b'A'..=b'Z' => ( byte - b'A' + 13 ) % 26 + b'A',
+//!         _ => byte,
+//!     }
+//! }
+//!
+//! impl Transform for Rot13 {
+//!     fn input( &mut self, data: &mut [u8] ) {
+//!         data.iter_mut().for_each( |byte| *byte = rot13( *byte ));
+//!     }
+//!     fn output<'a>( &mut self, data: &'a [u8] ) -> Cow<'a, [u8]> {
+//!         data.iter().map( |&byte| rot13( byte )).collect::<Vec<_>>().into()
+//!     }
+//! }
+//!
+//! let interp = Interpreter::new().unwrap();
+//! let chan = interp.eval( "file tempfile path" ).unwrap().to_string();
+//! interp.push_transform( &chan, Rot13 ).unwrap();
+//! interp.run(( "puts", "-nonewline", chan.as_str(), "Hello" )).unwrap();
+//! interp.run(( "close", chan.as_str() )).unwrap();
+//!
+//! let written = interp.eval( "set f [open $path]; set s [read $f]; close $f; set s" ).unwrap();
+//! assert_eq!( written.to_string(), "Uryyb" );
+//! ```
+
+use crate::{
+    Interp,
+    UnwrapOrAbort,
+    error::InterpError,
+};
+
+use std::{
+    borrow::Cow,
+    ffi::CString,
+    os::raw::{c_char, c_int},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    slice,
+};
+
+type Result<T, E=InterpError> = std::result::Result<T,E>;
+
+// The POSIX error code reported when a `Transform` panics, which is the same on Windows.
+const EIO: c_int = 5;
+
+/// Processing of the bytes flowing through a channel.
+pub trait Transform: 'static {
+    /// Processes in place the bytes read from the channel below, before the reader gets them.
+    fn input( &mut self, _data: &mut [u8] ) {}
+
+    /// Processes the bytes written to the channel, returning the bytes to write to the channel below.
+    fn output<'a>( &mut self, data: &'a [u8] ) -> Cow<'a, [u8]> { Cow::Borrowed( data )}
+
+    /// Returns the last bytes to write to the channel below, when the transform is popped or
+    /// the channel is closed.
+    fn finish( &mut self ) -> Vec<u8> { Vec::new() }
+}
+
+struct Stacked {
+    transform : Box<dyn Transform>,
+    parent    : clib::Tcl_Channel,
+    writable  : bool,
+}
+
+struct ChannelType( clib::Tcl_ChannelType );
+
+// The type only holds a static name and function pointers.
+unsafe impl Sync for ChannelType {}
+
+static CHANNEL_TYPE: ChannelType = ChannelType( clib::Tcl_ChannelType {
+    typeName         : c"rust-transform".as_ptr(),
+    version          : 5 as clib::Tcl_ChannelTypeVersion, // TCL_CHANNEL_VERSION_5
+    closeProc        : Some( close_proc ),
+    inputProc        : Some( input_proc ),
+    outputProc       : Some( output_proc ),
+    seekProc         : None,
+    setOptionProc    : None,
+    getOptionProc    : None,
+    watchProc        : Some( watch_proc ),
+    getHandleProc    : Some( get_handle_proc ),
+    close2Proc       : None,
+    blockModeProc    : None,
+    flushProc        : None,
+    handlerProc      : None,
+    wideSeekProc     : None,
+    threadActionProc : None,
+    truncateProc     : None,
+});
+
+// Writes all of `data` to `chan`, returning the error code on failure.
+unsafe fn write_all( chan: clib::Tcl_Channel, mut data: &[u8] ) -> std::result::Result<(), c_int> {
+    while !data.is_empty() {
+        let written = clib::Tcl_WriteRaw( chan, data.as_ptr() as *const c_char, data.len() as c_int );
+        if written <= 0 {
+            return Err( clib::Tcl_GetErrno() );
+        }
+        data = &data[ written as usize.. ];
+    }
+    Ok(())
+}
+
+// Panics of `Transform` must not unwind into Tcl, so they are reported as I/O errors.
+
+unsafe extern "C" fn close_proc( instance_data: clib::ClientData, _interp: *mut clib::Tcl_Interp ) -> c_int {
+    panic::catch_unwind( AssertUnwindSafe( || {
+        let mut stacked = Box::from_raw( instance_data as *mut Stacked );
+        let tail = stacked.transform.finish();
+        if stacked.writable {
+            if let Err( code ) = write_all( stacked.parent, &tail ) {
+                return code;
+            }
+        }
+        0
+    })).unwrap_or( EIO )
+}
+
+unsafe extern "C" fn input_proc( instance_data: clib::ClientData, buf: *mut c_char, to_read: c_int, error_code: *mut c_int ) -> c_int {
+    let stacked = &mut *( instance_data as *mut Stacked );
+    let read = clib::Tcl_ReadRaw( stacked.parent, buf, to_read );
+    if read < 0 {
+        *error_code = clib::Tcl_GetErrno();
+        return -1;
+    }
+    if read > 0 {
+        let data = slice::from_raw_parts_mut( buf as *mut u8, read as usize );
+        if panic::catch_unwind( AssertUnwindSafe( || stacked.transform.input( data ))).is_err() {
+            *error_code = EIO;
+            return -1;
+        }
+    }
+    read
+}
+
+unsafe extern "C" fn output_proc( instance_data: clib::ClientData, buf: *const c_char, to_write: c_int, error_code: *mut c_int ) -> c_int {
+    let stacked = &mut *( instance_data as *mut Stacked );
+    let data = if to_write > 0 { slice::from_raw_parts( buf as *const u8, to_write as usize )} else { &[][..] };
+    let written = panic::catch_unwind( AssertUnwindSafe( || {
+        let output = stacked.transform.output( data );
+        write_all( stacked.parent, &output )
+    })).unwrap_or( Err( EIO ));
+    match written {
+        Ok(()) => to_write,
+        Err( code ) => {
+            *error_code = code;
+            -1
+        },
+    }
+}
+
+unsafe extern "C" fn watch_proc( instance_data: clib::ClientData, mask: c_int ) {
+    panic::catch_unwind( || {
+        let parent = ( *( instance_data as *mut Stacked )).parent;
+        if let Some( watch ) = clib::Tcl_ChannelWatchProc( clib::Tcl_GetChannelType( parent )) {
+            watch( clib::Tcl_GetChannelInstanceData( parent ), mask );
+        }
+    }).unwrap_or_abort( "tcl::channel: watch_proc panicked." );
+}
+
+unsafe extern "C" fn get_handle_proc( instance_data: clib::ClientData, direction: c_int, handle: *mut clib::ClientData ) -> c_int {
+    panic::catch_unwind( || {
+        let parent = ( *( instance_data as *mut Stacked )).parent;
+        clib::Tcl_GetChannelHandle( parent, direction, handle )
+    }).unwrap_or_abort( "tcl::channel: get_handle_proc panicked." )
+}
+
+impl Interp {
+    /// Pushes `transform` onto the channel named `channel`, e.g. "stdout" or "file5".
+    /// Transforms pushed later are nearer to scripts using the channel.
+    pub fn push_transform( &self, channel: &str, transform: impl Transform ) -> Result<()> {
+        let name = CString::new( channel ).unwrap_or_default();
+        let chan = unsafe{ clib::Tcl_GetChannel( self.as_ptr(), name.as_ptr(), ptr::null_mut() )};
+        if chan.is_null() {
+            return Err( self.error() );
+        }
+
+        let mode = unsafe{ clib::Tcl_GetChannelMode( chan )};
+        let stacked = Box::into_raw( Box::new( Stacked {
+            transform : Box::new( transform ),
+            parent    : ptr::null_mut(),
+            writable  : mode & clib::TCL_WRITABLE as c_int != 0,
+        }));
+
+        let top = unsafe{ clib::Tcl_StackChannel( self.as_ptr(), &CHANNEL_TYPE.0, stacked as clib::ClientData, mode, chan )};
+        if top.is_null() {
+            drop( unsafe{ Box::from_raw( stacked )});
+            return Err( self.error() );
+        }
+        unsafe{ ( *stacked ).parent = clib::Tcl_GetStackedChannel( top ); }
+        Ok(())
+    }
+
+    /// Pops the topmost transform, pushed by Rust or by `chan push`, from the channel named
+    /// `channel`. Returns `false` if the channel has no transform. Unlike `chan pop`, it never
+    /// closes the channel.
+    pub fn pop_transform( &self, channel: &str ) -> Result<bool> {
+        let name = CString::new( channel ).unwrap_or_default();
+        let chan = unsafe{ clib::Tcl_GetChannel( self.as_ptr(), name.as_ptr(), ptr::null_mut() )};
+        if chan.is_null() {
+            return Err( self.error() );
+        }
+
+        let top = unsafe{ clib::Tcl_GetTopChannel( chan )};
+        if unsafe{ clib::Tcl_GetStackedChannel( top )}.is_null() {
+            return Ok( false );
+        }
+        if unsafe{ clib::Tcl_UnstackChannel( self.as_ptr(), top )} != clib::TCL_OK as c_int {
+            return Err( self.error() );
+        }
+        Ok( true )
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+    use super::Transform;
+
+    struct Upper;
+
+    impl Transform for Upper {
+        fn input( &mut self, data: &mut [u8] ) { data.make_ascii_uppercase(); }
+    }
+
+    #[test]
+    fn read_through_transform() {
+        let interp = Interpreter::new().unwrap();
+        interp.run( "set chan [file tempfile path]; puts -nonewline $chan hello; seek $chan 0" ).unwrap();
+
+        let chan = interp.eval( "set chan" ).unwrap().to_string();
+        interp.push_transform( &chan, Upper ).unwrap();
+        assert_eq!( interp.eval( "read $chan" ).unwrap().to_string(), "HELLO" );
+
+        assert!( interp.pop_transform( &chan ).unwrap() );
+        assert!( !interp.pop_transform( &chan ).unwrap() );
+        assert!( interp.push_transform( "no_such_channel", Upper ).is_err() );
+        interp.run( "close $chan" ).unwrap();
+    }
+}
@@ -217,6 +217,153 @@ impl Obj {
             remains ,
         })
     }
+
+    /// Borrows the dictionary for lookups, including nested ones.
+    /// An error of `NotDict` occurs if it cannot be converted to a dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcl::*;
+    ///
+    /// let dict = Obj::from(( "rust", ( "year", 2006, "typing", "static" )));
+    /// let dict = dict.dict_ref().unwrap();
+    /// assert_eq!( dict.get_path(( "rust", "year" )).unwrap().unwrap().as_i32(), 2006 );
+    /// assert!( dict.get_path(( "rust", "gc" )).unwrap().is_none() );
+    /// assert!( dict.get_path(( "rust", "year", "month" )).is_err() );
+    /// ```
+    pub fn dict_ref( &self ) -> Result<DictRef<'_>, NotDict> {
+        self.dict_size()?;
+        Ok( DictRef( self ))
+    }
+
+    /// Borrows the dictionary for modification. A shared obj is duplicated first, as in
+    /// `Obj::make_mut()`, so no `MutateSharedDict` error can occur.
+    /// An error of `NotDict` occurs if it cannot be converted to a dictionary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcl::*;
+    ///
+    /// let original = Obj::from(( "rust", ( "year", 2006 )));
+    /// let mut dict = original.clone();
+    /// {
+    ///     let mut dict = dict.dict_mut().unwrap();
+    ///     dict.put_path(( "rust", "typing" ), "static" ).unwrap();
+    ///     dict.put_path(( "tcl", "year" ), 1988 ).unwrap();
+    ///     dict.remove_path(( "rust", "year" )).unwrap();
+    /// }
+    /// assert_eq!( dict.to_string(), "rust {typing static} tcl {year 1988}" );
+    /// assert_eq!( original.to_string(), "rust {year 2006}" );
+    /// ```
+    pub fn dict_mut( &mut self ) -> Result<DictMut<'_>, NotDict> {
+        self.dict_size()?;
+        Ok( DictMut( self.make_mut() ))
+    }
+}
+
+// Looks up the value at the path of `keys`, as in `dict get $dict ...`.
+fn get_path( dict: &Obj, keys: Obj ) -> Result<Option<Obj>, NotDict> {
+    let mut value = dict.clone();
+    for key in keys.get_elements().map_err( |err| NotDict( err.0 ))? {
+        match value.dict_get( key )? {
+            Some( elem ) => value = elem,
+            None => return Ok( None ),
+        }
+    }
+    Ok( Some( value ))
+}
+
+fn key_list<K: Into<Obj>>( keys: impl IntoIterator<Item=K> ) -> Obj {
+    Obj::new_list( keys.into_iter().map( Into::into ))
+}
+
+/// A borrowed dictionary, created by `Obj::dict_ref()`.
+pub struct DictRef<'a>( &'a Obj );
+
+impl<'a> DictRef<'a> {
+    /// Returns the number of key-value pairs.
+    pub fn len( &self ) -> usize { self.0.dict_size().unwrap_or( 0 ) as usize }
+
+    /// Checks if the dictionary has no key.
+    pub fn is_empty( &self ) -> bool { self.len() == 0 }
+
+    /// Returns the value of `key`, or `None` if missing.
+    pub fn get( &self, key: impl Into<Obj> ) -> Option<Obj> { self.0.dict_get( key ).ok().flatten() }
+
+    /// Checks if the dictionary has `key`.
+    pub fn contains_key( &self, key: impl Into<Obj> ) -> bool { self.get( key ).is_some() }
+
+    /// Returns the value at the path of nested keys, as in `dict get $dict a b c`, or `None`
+    /// if any key is missing. An error of `NotDict` occurs if some intermediate value is not
+    /// a dictionary.
+    pub fn get_path( &self, keys: impl Into<Obj> ) -> Result<Option<Obj>, NotDict> {
+        get_path( self.0, keys.into() )
+    }
+
+    /// Returns an iterator over the key-value pairs.
+    pub fn iter( &self ) -> DictIter {
+        self.0.clone().dict_iter().expect( "DictRef should hold a dictionary" )
+    }
+}
+
+/// A dictionary borrowed for modification, created by `Obj::dict_mut()`.
+pub struct DictMut<'a>( &'a mut Obj );
+
+impl<'a> DictMut<'a> {
+    /// Borrows the dictionary for lookups.
+    pub fn view( &self ) -> DictRef<'_> { DictRef( self.0 )}
+
+    /// Returns the value of `key`, or `None` if missing.
+    pub fn get( &self, key: impl Into<Obj> ) -> Option<Obj> { self.view().get( key )}
+
+    /// Returns the value at the path of nested keys. See `DictRef::get_path()`.
+    pub fn get_path( &self, keys: impl Into<Obj> ) -> Result<Option<Obj>, NotDict> { self.view().get_path( keys )}
+
+    /// Maps `key` to `value`, replacing the old value if any.
+    pub fn put( &mut self, key: impl Into<Obj>, value: impl Into<Obj> ) {
+        self.put_path( key_list( Some( key )), value ).expect( "DictMut should hold a dictionary" );
+    }
+
+    /// Maps the path of nested keys to `value`, as in `dict set dict a b c value`, creating
+    /// missing intermediate dictionaries. Shared intermediate dictionaries are duplicated.
+    /// An error of `NotDict` occurs if some intermediate value is not a dictionary.
+    pub fn put_path( &mut self, keys: impl Into<Obj>, value: impl Into<Obj> ) -> Result<(), NotDict> {
+        let keys = keys.into().get_elements().map_err( |err| NotDict( err.0 ))?.collect::<Vec<_>>();
+        let value = value.into();
+        unsafe {
+            clib::Tcl_DictObjPutKeyList(
+                null_mut(),
+                self.0.as_ptr(),
+                keys.len() as c_int,
+                keys.as_ptr() as *const *mut clib::Tcl_Obj,
+                value.as_ptr(),
+        )}
+        .unit_result()
+        .map_err( |_| NotDict( self.0.clone() ))
+    }
+
+    /// Removes `key`. It is not an error if the key does not exist.
+    pub fn remove( &mut self, key: impl Into<Obj> ) {
+        self.remove_path( key_list( Some( key ))).expect( "DictMut should hold a dictionary" );
+    }
+
+    /// Removes the last key of the path from its enclosing nested dictionary, as in
+    /// `dict unset dict a b c`. It is not an error if the key does not exist, but an error
+    /// of `NotDict` occurs if some intermediate value is missing or not a dictionary.
+    pub fn remove_path( &mut self, keys: impl Into<Obj> ) -> Result<(), NotDict> {
+        let keys = keys.into().get_elements().map_err( |err| NotDict( err.0 ))?.collect::<Vec<_>>();
+        unsafe {
+            clib::Tcl_DictObjRemoveKeyList(
+                null_mut(),
+                self.0.as_ptr(),
+                keys.len() as c_int,
+                keys.as_ptr() as *const *mut clib::Tcl_Obj,
+        )}
+        .unit_result()
+        .map_err( |_| NotDict( self.0.clone() ))
+    }
 }
 
 /// An iterator that iterates each key-value pair.
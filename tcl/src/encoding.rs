@@ -0,0 +1,282 @@
+//! Conversion between Rust strings and external encodings supported by Tcl, e.g.
+//! "shiftjis", "cp1252" or "iso8859-2".
+//!
+//! Conversions are strict: an invalid byte sequence, or a character not representable in
+//! the target encoding, is reported as `InvalidSequence`. The `*_lossy` variants substitute
+//! a replacement character instead, as Tcl's `encoding convertfrom/convertto` do.
+
+use crate::error::{InvalidSequence, UnknownEncoding};
+
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
+use mutf8::mstr;
+
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int},
+    ptr,
+};
+
+const BUFFER_SIZE: usize = 4096;
+
+/// A Tcl encoding, freed when dropped.
+#[derive( Debug )]
+pub struct Encoding( clib::Tcl_Encoding );
+
+impl Encoding {
+    /// Looks up the encoding named `name`, as listed in `names()`.
+    pub fn get( name: &str ) -> Result<Self, UnknownEncoding> {
+        crate::init();
+
+        let c_name = CString::new( name ).map_err( |_| UnknownEncoding( name.to_owned() ))?;
+        let encoding = unsafe{ clib::Tcl_GetEncoding( ptr::null_mut(), c_name.as_ptr() )};
+        if encoding.is_null() {
+            Err( UnknownEncoding( name.to_owned() ))
+        } else {
+            Ok( Encoding( encoding ))
+        }
+    }
+
+    /// The system encoding.
+    pub fn system() -> Self {
+        crate::init();
+        Encoding( unsafe{ clib::Tcl_GetEncoding( ptr::null_mut(), clib::Tcl_GetEncodingName( ptr::null_mut() ))})
+    }
+
+    /// The name of this encoding.
+    pub fn name( &self ) -> String {
+        unsafe{ CStr::from_ptr( clib::Tcl_GetEncodingName( self.0 ))}.to_string_lossy().into_owned()
+    }
+
+    /// Creates a decoder converting bytes in this encoding to strings, chunk by chunk.
+    pub fn decoder( self ) -> Decoder { Decoder::new( self, clib::TCL_ENCODING_STOPONERROR as c_int )}
+
+    /// Creates an encoder converting strings to bytes in this encoding, chunk by chunk.
+    pub fn encoder( self ) -> Encoder { Encoder::new( self, clib::TCL_ENCODING_STOPONERROR as c_int )}
+}
+
+impl Drop for Encoding {
+    fn drop( &mut self ) {
+        unsafe{ clib::Tcl_FreeEncoding( self.0 ); }
+    }
+}
+
+/// Returns the names of all available encodings.
+pub fn names() -> Vec<String> {
+    crate::init();
+    unsafe {
+        let interp = clib::Tcl_CreateInterp();
+        clib::Tcl_GetEncodingNames( interp );
+        let names = crate::Obj::from_raw( clib::Tcl_GetObjResult( interp ))
+            .get_elements()
+            .map( |names| names.map( |name| name.get_string() ).collect() )
+            .unwrap_or_default();
+        clib::Tcl_DeleteInterp( interp );
+        names
+    }
+}
+
+/// Returns the name of the system encoding.
+pub fn system() -> String {
+    crate::init();
+    unsafe{ CStr::from_ptr( clib::Tcl_GetEncodingName( ptr::null_mut() ))}.to_string_lossy().into_owned()
+}
+
+/// Converts `bytes` in the encoding named `name` to a string.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::encoding;
+///
+/// let bytes = [0x82, 0xa0, 0x82, 0xa2]; // "あい" in Shift-JIS
+/// assert_eq!( encoding::convert_from( &bytes, "shiftjis" ).unwrap(), "あい" );
+/// assert!( encoding::convert_from( &[0x82], "shiftjis" ).is_err() );
+/// ```
+#[cex]
+pub fn convert_from( bytes: &[u8], name: &str ) -> Result!( String throws UnknownEncoding, InvalidSequence ) {
+    let mut decoder = Encoding::get( name )?.decoder();
+    let mut string = decoder.feed( bytes )?;
+    string.push_str( &decoder.finish()? );
+    Ok( string )
+}
+
+/// Converts `bytes` in the encoding named `name` to a string, replacing invalid sequences.
+pub fn convert_from_lossy( bytes: &[u8], name: &str ) -> Result<String, UnknownEncoding> {
+    let mut decoder = Decoder::new( Encoding::get( name )?, 0 );
+    let mut string = decoder.feed( bytes ).unwrap_or_default();
+    string.push_str( &decoder.finish().unwrap_or_default() );
+    Ok( string )
+}
+
+/// Converts `string` to bytes in the encoding named `name`.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::encoding;
+///
+/// assert_eq!( encoding::convert_to( "Grüße", "cp1252" ).unwrap(), b"Gr\xfc\xdfe" );
+/// assert!( encoding::convert_to( "あ", "iso8859-1" ).is_err() );
+/// ```
+#[cex]
+pub fn convert_to( string: &str, name: &str ) -> Result!( Vec<u8> throws UnknownEncoding, InvalidSequence ) {
+    let mut encoder = Encoding::get( name )?.encoder();
+    let mut bytes = encoder.feed( string )?;
+    bytes.extend( encoder.finish()? );
+    Ok( bytes )
+}
+
+/// Converts `string` to bytes in the encoding named `name`, replacing unrepresentable characters.
+pub fn convert_to_lossy( string: &str, name: &str ) -> Result<Vec<u8>, UnknownEncoding> {
+    let mut encoder = Encoder::new( Encoding::get( name )?, 0 );
+    let mut bytes = encoder.feed( string ).unwrap_or_default();
+    bytes.extend( encoder.finish().unwrap_or_default() );
+    Ok( bytes )
+}
+
+#[derive( Clone, Copy )]
+enum Direction {
+    ExternalToUtf,
+    UtfToExternal,
+}
+
+// The state shared by `Decoder` and `Encoder`.
+struct Converter {
+    encoding : Encoding,
+    flags    : c_int,
+    state    : clib::Tcl_EncodingState,
+    started  : bool,
+    pending  : Vec<u8>,
+    consumed : usize,
+}
+
+impl Converter {
+    fn new( encoding: Encoding, flags: c_int ) -> Self {
+        Converter{ encoding, flags, state: ptr::null_mut(), started: false, pending: Vec::new(), consumed: 0 }
+    }
+
+    // Converts pending bytes along with `src`. An incomplete sequence at the end is kept
+    // for the next call, unless `end` is true.
+    fn convert( &mut self, direction: Direction, src: &[u8], end: bool ) -> Result<Vec<u8>, InvalidSequence> {
+        self.pending.extend_from_slice( src );
+
+        let mut output = Vec::new();
+        let mut buffer = vec![ 0_u8; BUFFER_SIZE ];
+        let mut offset = 0;
+
+        loop {
+            let mut flags = self.flags;
+            if !self.started { flags |= clib::TCL_ENCODING_START as c_int; }
+            if end           { flags |= clib::TCL_ENCODING_END   as c_int; }
+
+            let (mut src_read, mut dst_wrote) = (0, 0);
+            let convert_proc = match direction {
+                Direction::ExternalToUtf => clib::Tcl_ExternalToUtf,
+                Direction::UtfToExternal => clib::Tcl_UtfToExternal,
+            };
+            let result = unsafe {
+                convert_proc(
+                    ptr::null_mut(),
+                    self.encoding.0,
+                    self.pending[ offset.. ].as_ptr() as *const c_char,
+                    ( self.pending.len() - offset ) as c_int,
+                    flags,
+                    &mut self.state,
+                    buffer.as_mut_ptr() as *mut c_char,
+                    buffer.len() as c_int,
+                    &mut src_read,
+                    &mut dst_wrote,
+                    ptr::null_mut(),
+                )
+            };
+
+            self.started = true;
+            offset += src_read as usize;
+            output.extend_from_slice( &buffer[ ..dst_wrote as usize ]);
+
+            match result {
+                clib::TCL_CONVERT_NOSPACE => continue,
+                clib::TCL_CONVERT_MULTIBYTE if !end => break,
+                code if code == clib::TCL_OK as c_int => break,
+                _ => {
+                    let error = InvalidSequence {
+                        encoding : self.encoding.name(),
+                        offset   : self.consumed + offset,
+                    };
+                    self.consumed += offset;
+                    self.pending.drain( ..offset );
+                    return Err( error );
+                },
+            }
+        }
+
+        self.consumed += offset;
+        self.pending.drain( ..offset );
+        Ok( output )
+    }
+}
+
+/// Converts bytes in some encoding to strings, accepting the input in arbitrary chunks.
+/// A multi-byte sequence split between chunks is completed by the next chunk.
+pub struct Decoder( Converter );
+
+impl Decoder {
+    fn new( encoding: Encoding, flags: c_int ) -> Self { Decoder( Converter::new( encoding, flags ))}
+
+    /// Converts the next chunk of bytes. On error, the bytes before the invalid sequence are
+    /// consumed and discarded.
+    pub fn feed( &mut self, bytes: &[u8] ) -> Result<String, InvalidSequence> {
+        self.0.convert( Direction::ExternalToUtf, bytes, false ).map( |utf| mutf8_to_string( &utf ))
+    }
+
+    /// Converts the remaining bytes, failing if they are an incomplete sequence.
+    pub fn finish( mut self ) -> Result<String, InvalidSequence> {
+        self.0.convert( Direction::ExternalToUtf, &[], true ).map( |utf| mutf8_to_string( &utf ))
+    }
+}
+
+/// Converts strings to bytes in some encoding, chunk by chunk.
+pub struct Encoder( Converter );
+
+impl Encoder {
+    fn new( encoding: Encoding, flags: c_int ) -> Self { Encoder( Converter::new( encoding, flags ))}
+
+    /// Converts the next chunk of string. On error, the characters before the unrepresentable
+    /// one are consumed and discarded.
+    pub fn feed( &mut self, string: &str ) -> Result<Vec<u8>, InvalidSequence> {
+        let utf = mstr::from_utf8( string.as_bytes() );
+        self.0.convert( Direction::UtfToExternal, utf.as_bytes(), false )
+    }
+
+    /// Finishes the conversion, emitting the bytes stateful encodings need to return to
+    /// their initial state, e.g. the escape sequence of "iso2022-jp".
+    pub fn finish( mut self ) -> Result<Vec<u8>, InvalidSequence> {
+        self.0.convert( Direction::UtfToExternal, &[], true )
+    }
+}
+
+// Converts the output of Tcl, which is always modified UTF-8, so it is not validated.
+fn mutf8_to_string( utf: &[u8] ) -> String {
+    mstr::from_mutf8_unchecked( utf ).to_utf8().into_owned()
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_decoding() {
+        assert!( names().iter().any( |name| name == "shiftjis" ));
+
+        let mut decoder = Encoding::get( "shiftjis" ).unwrap().decoder();
+        assert_eq!( decoder.feed( &[0x82, 0xa0, 0x82] ).unwrap(), "あ" );
+        assert_eq!( decoder.feed( &[0xa2] ).unwrap(), "い" );
+        assert_eq!( decoder.finish().unwrap(), "" );
+
+        assert!( Encoding::get( "no-such-encoding" ).is_err() );
+        assert_eq!( convert_to_lossy( "aあ", "iso8859-1" ).unwrap(), b"a?" );
+    }
+}
@@ -221,6 +221,19 @@ impl InterpError {
 
     /// Returns the value of `-errorcode` option.
     pub fn code( &self ) -> Obj { self.get_value( b"-errorcode\0" )}
+
+    fn code_starts_with( &self, prefix: &[&str] ) -> bool {
+        self.code()
+            .get_elements()
+            .map( |elems| elems.zip( prefix ).filter( |(elem, word)| elem.get_string() == **word ).count() == prefix.len() )
+            .unwrap_or( false )
+    }
+
+    /// Checks if the error is caused by cancelling the script evaluation, e.g. via `CancelToken::cancel()`.
+    pub fn is_cancelled( &self ) -> bool { self.code_starts_with( &[ "TCL", "CANCEL" ])}
+
+    /// Checks if the error is caused by exceeding a resource limit of the interpreter.
+    pub fn is_limit_exceeded( &self ) -> bool { self.code_starts_with( &[ "TCL", "LIMIT" ])}
 }
 
 impl Debug for InterpError {
@@ -237,6 +250,50 @@ impl Display for InterpError {
 
 impl std::error::Error for InterpError {}
 
+/// The script evaluation has been cancelled by `CancelToken`.
+#[derive( Debug )]
+pub struct Cancelled( pub InterpError );
+impl_std_error!{ Cancelled }
+
+/// The script evaluation has exceeded its time limit.
+#[derive( Debug )]
+pub struct LimitExceeded( pub InterpError );
+impl_std_error!{ LimitExceeded }
+
+/// No encoding of the given name is known to Tcl.
+#[derive( Debug )]
+pub struct UnknownEncoding( pub String );
+impl_std_error!{ UnknownEncoding }
+
+/// Fails to convert between encodings because of an invalid byte sequence, or a character
+/// not representable in the target encoding.
+#[derive( Debug )]
+pub struct InvalidSequence {
+    /// The name of the external encoding.
+    pub encoding : String,
+    /// Offset of the offending sequence in the whole input, in bytes of the external
+    /// encoding when decoding, or in bytes of Tcl's internal UTF-8 when encoding.
+    pub offset   : usize,
+}
+impl_std_error!{ InvalidSequence }
+
+/// Fails to compile a regular expression.
+#[derive( Debug )]
+pub struct InvalidRegex( pub InterpError );
+impl_std_error!{ InvalidRegex }
+
+/// Fails to parse a script.
+#[derive( Debug )]
+pub struct ParseError {
+    /// The error message reported by Tcl.
+    pub message    : String,
+    /// Byte offset in the script where the error was detected.
+    pub offset     : usize,
+    /// Whether the script is merely incomplete, e.g. ends inside braces or quotes.
+    pub incomplete : bool,
+}
+impl_std_error!{ ParseError }
+
 crate_error!{
     #[derive( Debug )]
     pub enum TclError {
@@ -252,6 +309,12 @@ crate_error!{
         NotSeq           ,
         MutateSharedDict ,
         InterpError      ,
+        Cancelled        ,
+        LimitExceeded    ,
+        UnknownEncoding  ,
+        InvalidSequence  ,
+        InvalidRegex     ,
+        ParseError       ,
     }
 }
 
@@ -98,6 +98,20 @@ impl Drop for Interpreter {
 #[derive( Clone, Debug )]
 pub struct Interp( NonNull<clib::Tcl_Interp> );
 
+thread_local! {
+    // A bare interpreter for reporting errors of Tcl's C API which requires one.
+    // It is deleted when the thread exits.
+    static SCRATCH: Interpreter = unsafe {
+        crate::init();
+        Interpreter( Interp::from_raw( clib::Tcl_CreateInterp() ).expect( "Tcl_CreateInterp() should not return null." ))
+    };
+}
+
+/// Calls `f` with a bare interpreter owned by the current thread, for internal use.
+pub(crate) fn with_scratch<T>( f: impl FnOnce( &Interp ) -> T ) -> T {
+    SCRATCH.with( |scratch| f( scratch ))
+}
+
 /// API for registering Rust functions as Tcl commands.
 pub type ObjCmdProc = extern "C" fn( clib::ClientData, *mut clib::Tcl_Interp, c_int, *const *mut clib::Tcl_Obj ) -> c_int;
 
@@ -211,6 +211,14 @@ impl<T,E> UnwrapOrAbort for Result<T,E>
 
 mod after;
 
+mod app;
+pub use app::main;
+
+pub mod cancel;
+pub use cancel::CancelToken;
+
+pub mod channel;
+
 pub mod interp;
 pub use interp::{CodeToResult, Interpreter, Interp, ObjCmdProc};
 
@@ -226,6 +234,9 @@ pub use ser::{Serializer, to_c_str, to_string};
 mod de;
 pub use de::from_obj;
 
+pub mod encoding;
+pub use encoding::Encoding;
+
 pub mod error;
 pub use error::{
     IntoTclError,
@@ -234,14 +245,40 @@ pub use error::{
 };
 
 pub mod dict;
-pub use dict::DictIter;
+pub use dict::{DictIter, DictMut, DictRef};
 
 pub mod list;
+pub use list::{ListMut, ListRef};
+
+#[cfg( feature = "log" )]
+pub mod log_bridge;
+
+pub mod msgcat;
+pub use msgcat::Msgcat;
+
+#[cfg( unix )]
+pub mod notifier;
+
+pub mod parse;
+pub mod profile;
+pub use profile::{ProfileReport, Profiler};
+
+pub mod regexp;
+pub use regexp::Regex;
+
+pub mod repl;
+pub use repl::Repl;
+
+pub mod snapshot;
+pub use snapshot::InterpSnapshot;
 
 mod trace;
 
 mod update;
 
+pub mod zlib;
+pub use zlib::ZlibStream;
+
 static INIT: Once = Once::new();
 
 pub(crate) fn init() {
@@ -7,10 +7,11 @@ use crate::{
 };
 
 use std::{
+    ops::Index,
     os::raw::c_int,
     mem,
     ptr::null_mut,
-    slice,
+    slice::{self, SliceIndex},
 };
 
 type Result<T, E=NotList> = std::result::Result<T,E>;
@@ -87,6 +88,8 @@ impl Obj {
     /// assert_eq!( elems.next().unwrap().to_string(), "answer" );
     /// assert_eq!( elems.next().unwrap().to_string(), "is" );
     /// assert_eq!( elems.next().unwrap().as_i32(), 42 );
+    ///
+    /// assert_eq!( Obj::new().get_elements().unwrap().count(), 0 );
     /// ```
     pub fn get_elements( self ) -> Result<impl Iterator<Item=Obj>> {
         let mut objc = 0;
@@ -96,11 +99,16 @@ impl Obj {
             .unit_result()
             .map_err( |_| NotList( self ))?;
 
-            unsafe {
-                slice::from_raw_parts( objv, objc as usize )
-                    .iter()
-                    .map( |tcl_obj| Obj::from_raw( *tcl_obj ))
-                    .collect::<Vec<_>>()
+            // `objv` may be NULL for an empty list.
+            if objc == 0 {
+                Vec::new()
+            } else {
+                unsafe {
+                    slice::from_raw_parts( objv, objc as usize )
+                        .iter()
+                        .map( |tcl_obj| Obj::from_raw( *tcl_obj ))
+                        .collect::<Vec<_>>()
+                }
             }
         };
         Ok( objs.into_iter() )
@@ -179,4 +187,163 @@ impl Obj {
         mem::forget( objs );
         result
     }
+
+    /// Borrows the elements of the list without copying them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// let list = Obj::from(( "The", "answer", "is", 42 ));
+    /// let elems = list.list_ref().unwrap();
+    /// assert_eq!( elems.len(), 4 );
+    /// assert_eq!( elems[1].to_string(), "answer" );
+    /// assert_eq!( elems[2..].iter().map( |elem| elem.to_string() ).collect::<Vec<_>>(), vec![ "is", "42" ]);
+    /// ```
+    pub fn list_ref( &self ) -> Result<ListRef<'_>> {
+        let mut objc = 0;
+        let mut objv = null_mut();
+        unsafe{ clib::Tcl_ListObjGetElements( null_mut(), self.as_ptr(), &mut objc, &mut objv )}
+        .unit_result()
+        .map_err( |_| NotList( self.clone() ))?;
+
+        // The duplicate shares the element array with `self`, and is never exposed, so the array
+        // stays valid even if `self` is modified or converted to another type.
+        let pinned = unsafe{ Obj::from_raw( clib::Tcl_DuplicateObj( self.as_ptr() ))};
+        unsafe{ clib::Tcl_ListObjGetElements( null_mut(), pinned.as_ptr(), &mut objc, &mut objv )}
+        .unit_result()
+        .map_err( |_| NotList( self.clone() ))?;
+
+        let elems = if objc == 0 {
+            &[][..]
+        } else {
+            unsafe{ slice::from_raw_parts( objv as *const Obj, objc as usize )}
+        };
+        Ok( ListRef{ _pinned: pinned, elems })
+    }
+
+    /// Borrows the list for modification. A shared obj is duplicated first, as in `Obj::make_mut()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// let original = Obj::from(( "The", "answer", "is", 42 ));
+    /// let mut list = original.clone();
+    /// {
+    ///     let mut elems = list.list_mut().unwrap();
+    ///     elems.set( 3, "unknown" );
+    ///     elems.remove( 0 );
+    ///     elems.push( "yet" );
+    /// }
+    /// assert_eq!( list.to_string(), "answer is unknown yet" );
+    /// assert_eq!( original.to_string(), "The answer is 42" );
+    /// ```
+    pub fn list_mut( &mut self ) -> Result<ListMut<'_>> {
+        self.list_length()?;
+        Ok( ListMut( self.make_mut() ))
+    }
+}
+
+/// A borrowed view of the elements of a list obj, created by `Obj::list_ref()`.
+pub struct ListRef<'a> {
+    _pinned : Obj,
+    elems   : &'a [Obj],
+}
+
+impl<'a> ListRef<'a> {
+    /// Returns the number of elements.
+    pub fn len( &self ) -> usize { self.elems.len() }
+
+    /// Checks if the list has no element.
+    pub fn is_empty( &self ) -> bool { self.elems.is_empty() }
+
+    /// Returns the element at `index`, or `None` if out of bounds.
+    pub fn get( &self, index: usize ) -> Option<&Obj> { self.elems.get( index )}
+
+    /// Returns an iterator over the elements.
+    pub fn iter( &self ) -> slice::Iter<'_, Obj> { self.elems.iter() }
+
+    /// Returns the elements as a slice.
+    pub fn as_slice( &self ) -> &[Obj] { self.elems }
+}
+
+impl<'a, I: SliceIndex<[Obj]>> Index<I> for ListRef<'a> {
+    type Output = I::Output;
+
+    fn index( &self, index: I ) -> &Self::Output { &self.elems[ index ]}
+}
+
+impl<'a, 'r> IntoIterator for &'r ListRef<'a> {
+    type Item = &'r Obj;
+    type IntoIter = slice::Iter<'r, Obj>;
+
+    fn into_iter( self ) -> Self::IntoIter { self.elems.iter() }
+}
+
+/// A list obj borrowed for modification, created by `Obj::list_mut()`.
+/// Like `Vec`, it panics on out-of-bounds indices.
+pub struct ListMut<'a>( &'a mut Obj );
+
+impl<'a> ListMut<'a> {
+    /// Returns the number of elements.
+    pub fn len( &self ) -> usize { self.0.list_length().unwrap_or( 0 ) as usize }
+
+    /// Checks if the list has no element.
+    pub fn is_empty( &self ) -> bool { self.len() == 0 }
+
+    /// Returns the element at `index`, or `None` if out of bounds.
+    pub fn get( &self, index: usize ) -> Option<Obj> { self.0.list_index( index as c_int ).ok().flatten() }
+
+    /// Borrows the elements without copying them.
+    pub fn view( &self ) -> ListRef<'_> { self.0.list_ref().expect( "ListMut should hold a list" )}
+
+    /// Appends an element to the end of the list.
+    pub fn push( &mut self, elem: impl Into<Obj> ) {
+        self.splice( self.len(), 0, Some( elem.into() ));
+    }
+
+    /// Appends the elements of `elems` to the end of the list.
+    pub fn extend<T: Into<Obj>>( &mut self, elems: impl IntoIterator<Item=T> ) {
+        self.splice( self.len(), 0, elems.into_iter().map( Into::into ));
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it.
+    pub fn insert( &mut self, index: usize, elem: impl Into<Obj> ) {
+        let len = self.len();
+        assert!( index <= len, "insertion index (is {}) should be <= len (is {})", index, len );
+        self.splice( index, 0, Some( elem.into() ));
+    }
+
+    /// Replaces the element at `index`, returning the old one.
+    pub fn set( &mut self, index: usize, elem: impl Into<Obj> ) -> Obj {
+        let old = self.expect_index( index );
+        self.splice( index, 1, Some( elem.into() ));
+        old
+    }
+
+    /// Removes and returns the element at `index`, shifting all elements after it.
+    pub fn remove( &mut self, index: usize ) -> Obj {
+        let old = self.expect_index( index );
+        self.splice( index, 1, None );
+        old
+    }
+
+    /// Keeps the first `len` elements and drops the rest.
+    pub fn truncate( &mut self, len: usize ) {
+        let old_len = self.len();
+        if len < old_len {
+            self.splice( len, old_len - len, None );
+        }
+    }
+
+    fn expect_index( &self, index: usize ) -> Obj {
+        let len = self.len();
+        self.get( index ).unwrap_or_else( || panic!( "index out of bounds: the len is {} but the index is {}", len, index ))
+    }
+
+    fn splice( &mut self, first: usize, count: usize, objs: impl IntoIterator<Item=Obj> ) {
+        self.0.list_replace( first as c_int, count as c_int, objs.into_iter() )
+            .expect( "ListMut should hold a list" );
+    }
 }
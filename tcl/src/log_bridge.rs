@@ -0,0 +1,168 @@
+//! Routes Tcl's background errors and standard output to the `log` crate, so that
+//! script failures end up in the application's normal logs. Requires the "log" feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::*;
+//! use tcl::log_bridge::{Level, LogBridge};
+//!
+//! let interp = Interpreter::new().unwrap();
+//! LogBridge::new()
+//!     .target( "app::script" )
+//!     .stdout( Level::Info )
+//!     .stderr( Level::Warn )
+//!     .install( &interp )
+//!     .unwrap();
+//!
+//! // Both are logged instead of printed, with no logger installed here.
+//! interp.run( "puts {hello, log}; puts stderr {something odd}" ).unwrap();
+//! ```
+
+use crate::{
+    Interp,
+    Obj,
+    channel::Transform,
+    error::InterpError,
+};
+
+pub use log::Level;
+
+use std::{
+    borrow::Cow,
+    rc::Rc,
+};
+
+type Result<T, E=InterpError> = std::result::Result<T,E>;
+
+/// Builder of the bridge from an interpreter to the `log` crate.
+pub struct LogBridge {
+    target   : String,
+    handler  : Option<Rc<dyn Fn( InterpError )>>,
+    stdout   : Option<Level>,
+    stderr   : Option<Level>,
+}
+
+impl Default for LogBridge {
+    fn default() -> Self { LogBridge::new() }
+}
+
+impl LogBridge {
+    /// Creates a bridge logging background errors at `Error` level with target "tcl", and not
+    /// redirecting any channel.
+    pub fn new() -> Self {
+        LogBridge{ target: "tcl".to_owned(), handler: None, stdout: None, stderr: None }
+    }
+
+    /// Sets the target of the log records.
+    pub fn target( mut self, target: &str ) -> Self {
+        self.target = target.to_owned();
+        self
+    }
+
+    /// Handles background errors, e.g. errors in `after` scripts or Tk event bindings, with `f`
+    /// instead of logging them. The error's `info()` has the stack trace.
+    pub fn on_background_error( mut self, f: impl Fn( InterpError ) + 'static ) -> Self {
+        self.handler = Some( Rc::new( f ));
+        self
+    }
+
+    /// Logs each line written to `stdout` at `level`, instead of printing it.
+    pub fn stdout( mut self, level: Level ) -> Self {
+        self.stdout = Some( level );
+        self
+    }
+
+    /// Logs each line written to `stderr` at `level`, instead of printing it.
+    pub fn stderr( mut self, level: Level ) -> Self {
+        self.stderr = Some( level );
+        self
+    }
+
+    /// Installs the bridge in `interp`, replacing its `interp bgerror` handler, including the
+    /// error dialog of Tk.
+    pub fn install( self, interp: &Interp ) -> Result<()> {
+        use crate as tcl;
+
+        let target = self.target.clone();
+        let handler = self.handler.clone().unwrap_or_else( || Rc::new( move |error: InterpError| {
+            log::error!( target: &target, "{}", error.info() );
+        }));
+
+        let cmd = crate::tclosure!( interp,
+            move |message: String, options: String| -> tcl::TclResult<()> {
+                handler( InterpError{ obj: Obj::from( message ), options: Obj::from( options )});
+                Ok(())
+            }
+        );
+        interp.run(( "interp", "bgerror", "", cmd ))?;
+
+        if let Some( level ) = self.stdout {
+            interp.push_transform( "stdout", LogLines::new( &self.target, level ))?;
+        }
+        if let Some( level ) = self.stderr {
+            interp.push_transform( "stderr", LogLines::new( &self.target, level ))?;
+        }
+        Ok(())
+    }
+}
+
+// A channel transform swallowing the output and logging it line by line.
+struct LogLines {
+    target  : String,
+    level   : Level,
+    partial : Vec<u8>,
+}
+
+impl LogLines {
+    fn new( target: &str, level: Level ) -> Self {
+        LogLines{ target: target.to_owned(), level, partial: Vec::new() }
+    }
+
+    fn log( &self, line: &[u8] ) {
+        let line = String::from_utf8_lossy( line );
+        log::log!( target: &self.target, self.level, "{}", line.trim_end_matches( '\r' ));
+    }
+}
+
+impl Transform for LogLines {
+    fn output<'a>( &mut self, data: &'a [u8] ) -> Cow<'a, [u8]> {
+        self.partial.extend_from_slice( data );
+        while let Some( newline ) = self.partial.iter().position( |&byte| byte == b'\n' ) {
+            let rest = self.partial.split_off( newline + 1 );
+            self.log( &self.partial[ ..newline ]);
+            self.partial = rest;
+        }
+        Cow::Borrowed( &[] )
+    }
+
+    fn finish( &mut self ) -> Vec<u8> {
+        if !self.partial.is_empty() {
+            self.log( &self.partial );
+            self.partial.clear();
+        }
+        Vec::new()
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+    use super::LogBridge;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn background_error_handler() {
+        let interp = Interpreter::new().unwrap();
+        let caught = Rc::new( RefCell::new( None ));
+        let handled = caught.clone();
+
+        LogBridge::new()
+            .on_background_error( move |error| *handled.borrow_mut() = Some( error.to_string() ))
+            .install( &interp )
+            .unwrap();
+
+        interp.run( "after 0 {error boom}; update" ).unwrap();
+        assert_eq!( caught.borrow().as_deref(), Some( "boom" ));
+    }
+}
@@ -0,0 +1,313 @@
+//! Message catalogs for localizing user interfaces, backed by Tcl's `msgcat` package.
+//!
+//! Note that `msgcat` keeps separate catalogs for each namespace. A `Msgcat` works in the
+//! global namespace unless `Msgcat::with_namespace()` says otherwise.
+
+use crate::{
+    Obj,
+    error::InterpError,
+    interp::Interp,
+};
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    mem,
+    path::Path,
+    rc::{Rc, Weak},
+};
+
+type Result<T, E=InterpError> = std::result::Result<T,E>;
+
+// The command notified of locale changes, which owns the registry of localized widgets.
+const LOCALE_CHANGED: &str = "::tcl_rs_msgcat_locale_changed";
+
+// The command called by `::msgcat::mcunknown`.
+const UNKNOWN_HOOK: &str = "::tcl_rs_msgcat_unknown";
+
+struct Localized {
+    namespace : String,
+    path      : String,
+    option    : String,
+    src       : String,
+    args      : Vec<Obj>,
+}
+
+// Widget options localized in an interpreter, shared by all of its `Msgcat` handles.
+#[derive( Default )]
+struct Registry {
+    locale    : String,
+    localized : Vec<Localized>,
+}
+
+thread_local! {
+    static REGISTRIES: RefCell<HashMap<*mut clib::Tcl_Interp, Weak<RefCell<Registry>>>> = RefCell::new( HashMap::new() );
+}
+
+/// Handle of message catalogs in an interpreter, created by `Interp::msgcat()`.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::*;
+///
+/// let interpreter = Interpreter::new().unwrap();
+/// let msgcat = interpreter.msgcat()?;
+///
+/// msgcat.set_many( "de", [( "Quit", "Beenden" ), ( "%d files", "%d Dateien" )])?;
+/// msgcat.set_locale( "de" )?;
+///
+/// assert_eq!( msgcat.mc( "Quit" ), "Beenden" );
+/// assert_eq!( msgcat.mc_fmt( "%d files", [3] )?, "3 Dateien" );
+/// assert_eq!( msgcat.mc( "Help" ), "Help" );
+///
+/// # Ok::<(),TclError>(())
+/// ```
+#[derive( Clone )]
+pub struct Msgcat {
+    interp    : Interp,
+    namespace : String,
+    registry  : Rc<RefCell<Registry>>,
+}
+
+impl Interp {
+    /// Loads the `msgcat` package, returning a handle of the message catalogs.
+    pub fn msgcat( &self ) -> Result<Msgcat> {
+        self.run(( "package", "require", "msgcat" ))?;
+        Ok( Msgcat {
+            interp    : self.clone(),
+            namespace : "::".to_owned(),
+            registry  : self.msgcat_registry()?,
+        })
+    }
+
+    // Returns the registry of localized widgets of this interpreter, creating it on first use.
+    // The registry is owned by the command tracing `::msgcat::mclocale`, so it lives as long as
+    // the interpreter, and widgets get relocalized whoever changes the locale.
+    fn msgcat_registry( &self ) -> Result<Rc<RefCell<Registry>>> {
+        use crate as tcl;
+
+        let installed = !self.eval(( "info", "commands", LOCALE_CHANGED ))?.is_empty();
+        if installed {
+            let registry = REGISTRIES.with( |registries| registries.borrow()
+                .get( &self.as_ptr() )
+                .and_then( Weak::upgrade ));
+            if let Some( registry ) = registry {
+                return Ok( registry );
+            }
+        }
+
+        let locale = call( self, "::", "mclocale", vec![] )?.get_string();
+        let registry = Rc::new( RefCell::new( Registry{ locale, localized: Vec::new() }));
+        REGISTRIES.with( |registries| registries.borrow_mut().insert( self.as_ptr(), Rc::downgrade( &registry )));
+
+        let interp = self.clone();
+        let owned = registry.clone();
+        let _ = crate::tclosure!( self, cmd: LOCALE_CHANGED,
+            move |_command: String, code: i32, locale: String, _op: String| -> tcl::TclResult<()> {
+                if code == clib::TCL_OK as i32 && owned.borrow().locale != locale {
+                    owned.borrow_mut().locale = locale;
+                    relocalize( &interp, &owned );
+                }
+                Ok(())
+            }
+        );
+        self.run(( "trace", "add", "execution", "::msgcat::mclocale", "leave", LOCALE_CHANGED ))?;
+        Ok( registry )
+    }
+}
+
+fn call( interp: &Interp, namespace: &str, cmd: &str, args: Vec<Obj> ) -> Result<Obj> {
+    let mut command = Vec::with_capacity( args.len() + 1 );
+    command.push( Obj::from( format!( "::msgcat::{}", cmd )));
+    command.extend( args );
+    interp.eval(( "namespace", "eval", namespace, Obj::from( command )))
+}
+
+fn apply( interp: &Interp, localized: &Localized ) -> Result<()> {
+    let mut args = vec![ Obj::from( localized.src.as_str() )];
+    args.extend( localized.args.iter().cloned() );
+    let text = call( interp, &localized.namespace, "mc", args )?;
+    interp.run(( localized.path.as_str(), "configure", localized.option.as_str(), text ))
+}
+
+// The registry is not borrowed while widgets are configured, which may register more of them.
+fn relocalize( interp: &Interp, registry: &RefCell<Registry> ) {
+    let mut localized = mem::take( &mut registry.borrow_mut().localized );
+    localized.retain( |localized| apply( interp, localized ).is_ok() );
+
+    let mut registry = registry.borrow_mut();
+    let added = mem::take( &mut registry.localized );
+    localized.retain( |old| !added.iter().any( |new| new.path == old.path && new.option == old.option ));
+    localized.extend( added );
+    registry.localized = localized;
+}
+
+impl Msgcat {
+    /// Uses the message catalog of `namespace` instead of the global one.
+    pub fn with_namespace( mut self, namespace: &str ) -> Self {
+        self.namespace = namespace.to_owned();
+        self
+    }
+
+    fn call( &self, cmd: &str, args: Vec<Obj> ) -> Result<Obj> {
+        call( &self.interp, &self.namespace, cmd, args )
+    }
+
+    /// The current locale, e.g. "de_de", in lower case.
+    pub fn locale( &self ) -> String {
+        self.call( "mclocale", vec![] )
+            .map( |locale| locale.get_string() )
+            .unwrap_or_default()
+    }
+
+    /// Changes the current locale, then re-applies translations to the widget options
+    /// registered by `localize()` and `localize_fmt()`. So does `mclocale` called by Tcl scripts.
+    pub fn set_locale( &self, locale: &str ) -> Result<()> {
+        self.call( "mclocale", vec![ locale.into() ]).map( |_| () )
+    }
+
+    /// The locales searched for translations, from the most specific one to the root locale "".
+    pub fn preferences( &self ) -> Vec<String> {
+        self.call( "mcpreferences", vec![] ).ok()
+            .and_then( |locales| locales.get_elements().ok() )
+            .map( |locales| locales.map( |locale| locale.get_string() ).collect() )
+            .unwrap_or_default()
+    }
+
+    /// Defines the translation of `src` in `locale`.
+    pub fn set( &self, locale: &str, src: &str, translation: &str ) -> Result<()> {
+        self.call( "mcset", vec![ locale.into(), src.into(), translation.into() ]).map( |_| () )
+    }
+
+    /// Defines translations in `locale` from pairs of source strings and translations, e.g.
+    /// a `HashMap`. Returns the number of translations defined.
+    pub fn set_many<K,V>( &self, locale: &str, translations: impl IntoIterator<Item=(K,V)> ) -> Result<usize>
+        where K: Into<Obj>
+            , V: Into<Obj>
+    {
+        let pairs = translations
+            .into_iter()
+            .flat_map( |(src, translation)| [ src.into(), translation.into() ])
+            .collect::<Vec<Obj>>();
+        let count = pairs.len() / 2;
+        self.call( "mcmset", vec![ locale.into(), pairs.into() ])?;
+        Ok( count )
+    }
+
+    /// Sources the `.msg` files in `dir` which match the preferred locales.
+    /// Returns the number of files loaded.
+    pub fn load( &self, dir: impl AsRef<Path> ) -> Result<usize> {
+        let count = self.call( "mcload", vec![ dir.as_ref().to_string_lossy().into() ])?;
+        Ok( self.interp.int( count )? as usize )
+    }
+
+    /// Checks if `src` has a translation in the preferred locales.
+    pub fn exists( &self, src: &str ) -> bool {
+        self.call( "mcexists", vec![ src.into() ])
+            .and_then( |exists| self.interp.boolean( exists ))
+            .unwrap_or( false )
+    }
+
+    /// Translates `src` according to the current locale. Returns `src` itself if no
+    /// translation is found and no `on_unknown()` hook is installed.
+    pub fn mc( &self, src: &str ) -> String {
+        self.call( "mc", vec![ src.into() ])
+            .map( |translation| translation.get_string() )
+            .unwrap_or_else( |_| src.to_owned() )
+    }
+
+    /// Translates `src` according to the current locale, then substitutes `args` for the
+    /// conversion specifiers in it, as Tcl's `format` does.
+    pub fn mc_fmt<A: Into<Obj>>( &self, src: &str, args: impl IntoIterator<Item=A> ) -> Result<String> {
+        let mut words = vec![ Obj::from( src )];
+        words.extend( args.into_iter().map( Into::into ));
+        self.call( "mc", words ).map( |translation| translation.get_string() )
+    }
+
+    /// Installs `f` as the `mcunknown` hook, which is called with the current locale and the
+    /// source string when no translation is found. Its result is used as the translation,
+    /// before substitution of format arguments. It replaces the hook installed previously.
+    pub fn on_unknown( &self, f: impl Fn( &str, &str ) -> String + 'static ) -> Result<()> {
+        use crate as tcl;
+
+        let cmd = crate::tclosure!( &self.interp, cmd: UNKNOWN_HOOK,
+            move |locale: String, src: String| -> tcl::TclResult<String> { Ok( f( &locale, &src ))}
+        );
+
+        self.interp.run(( "proc", "::msgcat::mcunknown", "locale src args", format!( r#"
+            set translation [{} $locale $src]
+            if {{[llength $args]}} {{
+                return [format $translation {{*}}$args]
+            }}
+            return $translation
+        "#, cmd )))
+    }
+
+    /// Sets the `option` of the widget at `path` to the translation of `src`, and does it
+    /// again whenever the locale is changed.
+    pub fn localize( &self, path: &str, option: &str, src: &str ) -> Result<()> {
+        self.localize_fmt( path, option, src, Vec::<Obj>::new() )
+    }
+
+    /// Sets the `option` of the widget at `path` to the translation of `src` formatted with
+    /// `args`, and does it again whenever the locale is changed.
+    pub fn localize_fmt<A: Into<Obj>>( &self, path: &str, option: &str, src: &str, args: impl IntoIterator<Item=A> ) -> Result<()> {
+        let localized = Localized {
+            namespace : self.namespace.clone(),
+            path      : path.to_owned(),
+            option    : format!( "-{}", option.trim_start_matches( '-' )),
+            src       : src.to_owned(),
+            args      : args.into_iter().map( Into::into ).collect(),
+        };
+        apply( &self.interp, &localized )?;
+
+        let mut registry = self.registry.borrow_mut();
+        registry.localized.retain( |old| old.path != localized.path || old.option != localized.option );
+        registry.localized.push( localized );
+        Ok(())
+    }
+
+    /// Re-applies translations to the widget options registered by `localize()` and
+    /// `localize_fmt()`. Registrations of widgets that fail to configure, e.g. destroyed
+    /// ones, are dropped.
+    pub fn relocalize( &self ) {
+        relocalize( &self.interp, &self.registry );
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn localize_and_unknown() {
+        let interpreter = Interpreter::new().unwrap();
+        interpreter.run( "proc .label {cmd opt value} { set ::label($opt) $value }" ).unwrap();
+
+        let msgcat = interpreter.msgcat().unwrap();
+        msgcat.set( "de", "Quit", "Beenden" ).unwrap();
+        msgcat.set( "ja", "Quit", "終了" ).unwrap();
+        msgcat.set_locale( "de" ).unwrap();
+
+        msgcat.localize( ".label", "text", "Quit" ).unwrap();
+        assert_eq!( interpreter.arr_get( "label", "-text" ).unwrap().get_string(), "Beenden" );
+
+        msgcat.set_locale( "ja" ).unwrap();
+        assert_eq!( interpreter.arr_get( "label", "-text" ).unwrap().get_string(), "終了" );
+
+        // another handle, or Tcl scripts
+        interpreter.msgcat().unwrap().set_locale( "de" ).unwrap();
+        assert_eq!( interpreter.arr_get( "label", "-text" ).unwrap().get_string(), "Beenden" );
+        interpreter.run( "::msgcat::mclocale ja" ).unwrap();
+        assert_eq!( interpreter.arr_get( "label", "-text" ).unwrap().get_string(), "終了" );
+
+        msgcat.on_unknown( |locale, src| format!( "{}:{}", locale, src )).unwrap();
+        assert_eq!( msgcat.mc( "Help" ), "ja:Help" );
+        assert!( !msgcat.exists( "Help" ));
+
+        interpreter.run( "rename .label {}" ).unwrap();
+        msgcat.relocalize();
+        assert!( msgcat.registry.borrow().localized.is_empty() );
+    }
+}
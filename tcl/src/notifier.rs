@@ -0,0 +1,468 @@
+//! Integration of Tcl's event loop with other event loops.
+//!
+//! Two directions are supported.
+//!
+//! * Rust event sources driven by Tcl's event loop, e.g. `tk::main_loop()`: a
+//!   `FileHandler` watches a file descriptor such as the one of a `mio::Poll`, and calls
+//!   a Rust closure from inside `Tcl_DoOneEvent()`.
+//!
+//! * Tcl's event loop driven by another event loop, e.g. tokio or calloop (Linux only):
+//!   `install()` replaces Tcl's notifier with an epoll based one. The foreign event loop
+//!   watches `as_raw_fd()` for readability and wakes up after `timeout()`, then calls
+//!   `process_events()` to let Tcl handle its pending work.
+
+use crate::UnwrapOrAbort;
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    os::{
+        raw::{c_int, c_void},
+        unix::io::RawFd,
+    },
+    panic::{self, AssertUnwindSafe},
+};
+
+/// The file descriptor is readable.
+pub const READABLE : c_int = clib::TCL_READABLE  as c_int;
+/// The file descriptor is writable.
+pub const WRITABLE : c_int = clib::TCL_WRITABLE  as c_int;
+/// An exceptional condition is present on the file descriptor.
+pub const EXCEPTION: c_int = clib::TCL_EXCEPTION as c_int;
+
+type Callback = Box<dyn FnMut( c_int )>;
+
+thread_local! {
+    // The callback currently registered for each file descriptor by `FileHandler`.
+    static FILE_HANDLERS: RefCell<HashMap<RawFd, *mut Callback>> = RefCell::new( HashMap::new() );
+}
+
+/// Calls a Rust closure from Tcl's event loop when a file descriptor becomes ready.
+/// The handler is removed when dropped.
+///
+/// Tcl keeps at most one handler for each file descriptor, so creating a new handler
+/// replaces the existing one of the same file descriptor. Dropping the replaced handler
+/// does not affect the new one.
+///
+/// # Example
+///
+/// ```rust
+/// use std::{cell::Cell, io::Write, os::unix::io::AsRawFd, rc::Rc};
+/// use tcl::*;
+/// use tcl::notifier::{FileHandler, READABLE};
+///
+/// let _interpreter = Interpreter::new().unwrap();
+/// let (reader, mut writer) = std::os::unix::net::UnixStream::pair().unwrap();
+///
+/// let ready = Rc::new( Cell::new( false ));
+/// let ready_ = ready.clone();
+/// let _handler = FileHandler::new( reader.as_raw_fd(), READABLE, move |_mask| ready_.set( true ));
+///
+/// writer.write_all( b"ping" ).unwrap();
+/// while !ready.get() {
+///     unsafe{ clib::Tcl_DoOneEvent( 0 ); }
+/// }
+/// ```
+pub struct FileHandler {
+    fd       : RawFd,
+    callback : *mut Callback,
+}
+
+impl FileHandler {
+    /// Watches `fd` for the conditions in `mask`, which is a combination of `READABLE`,
+    /// `WRITABLE` and `EXCEPTION`. The `callback` receives the conditions present.
+    pub fn new( fd: RawFd, mask: c_int, callback: impl FnMut( c_int ) + 'static ) -> Self {
+        crate::init();
+
+        let callback = Box::into_raw( Box::new( Box::new( callback ) as Callback ));
+        unsafe {
+            clib::Tcl_CreateFileHandler( fd, mask, Some( file_handler_proc ), callback as clib::ClientData );
+        }
+        FILE_HANDLERS.with( |handlers| handlers.borrow_mut().insert( fd, callback ));
+        FileHandler{ fd, callback }
+    }
+
+    /// The watched file descriptor.
+    pub fn fd( &self ) -> RawFd { self.fd }
+}
+
+impl Drop for FileHandler {
+    fn drop( &mut self ) {
+        let owned = FILE_HANDLERS.with( |handlers| {
+            let mut handlers = handlers.borrow_mut();
+            if handlers.get( &self.fd ) == Some( &self.callback ) {
+                handlers.remove( &self.fd );
+                true
+            } else {
+                false
+            }
+        });
+        unsafe {
+            if owned {
+                clib::Tcl_DeleteFileHandler( self.fd );
+            }
+            drop( Box::from_raw( self.callback ));
+        }
+    }
+}
+
+unsafe extern "C" fn file_handler_proc( client_data: clib::ClientData, mask: c_int ) {
+    let callback = &mut *( client_data as *mut Callback );
+    panic::catch_unwind( AssertUnwindSafe( || callback( mask )))
+        .unwrap_or_abort( "A tcl::notifier::FileHandler callback panicked." );
+}
+
+#[cfg( target_os = "linux" )]
+pub use self::epoll::{as_raw_fd, install, is_installed, process_events, timeout};
+
+#[cfg( target_os = "linux" )]
+mod epoll {
+    use super::*;
+
+    use std::{
+        ffi::CString,
+        mem,
+        ptr,
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
+
+    const WAKE_TOKEN: u64 = u64::MAX;
+    const MAX_EVENTS: usize = 64;
+
+    static INSTALLED: AtomicBool = AtomicBool::new( false );
+
+    struct Handler {
+        mask        : c_int,
+        ready       : c_int,
+        proc_       : clib::Tcl_FileProc,
+        client_data : clib::ClientData,
+    }
+
+    struct Notifier {
+        epfd     : RawFd,
+        wake     : RawFd,
+        handlers : HashMap<RawFd, Handler>,
+        deadline : Option<Instant>,
+        probing  : bool,
+    }
+
+    thread_local! {
+        static NOTIFIER: RefCell<Option<Notifier>> = const{ RefCell::new( None )};
+    }
+
+    #[repr( C )]
+    struct FileEvent {
+        header : clib::Tcl_Event,
+        fd     : RawFd,
+    }
+
+    /// Replaces Tcl's notifier with an epoll based one, which can be driven by other event
+    /// loops via `as_raw_fd()`, `timeout()` and `process_events()`. It also serves
+    /// `Tcl_DoOneEvent()` as usual, so `tk::main_loop()` keeps working.
+    ///
+    /// This function must be called before any `Interpreter` or `Obj` is created.
+    /// Returns false if it is too late to replace the notifier.
+    pub fn install() -> bool {
+        if crate::INIT.is_completed() {
+            return INSTALLED.load( Ordering::SeqCst );
+        }
+
+        let mut procs = clib::Tcl_NotifierProcs {
+            setTimerProc          : Some( set_timer             ),
+            waitForEventProc      : Some( wait_for_event        ),
+            createFileHandlerProc : Some( create_file_handler   ),
+            deleteFileHandlerProc : Some( delete_file_handler   ),
+            initNotifierProc      : Some( init_notifier         ),
+            finalizeNotifierProc  : Some( finalize_notifier     ),
+            alertNotifierProc     : Some( alert_notifier        ),
+            serviceModeHookProc   : Some( service_mode_hook     ),
+        };
+
+        unsafe{ clib::Tcl_SetNotifier( &mut procs ); }
+        INSTALLED.store( true, Ordering::SeqCst );
+        true
+    }
+
+    /// Checks if the notifier provided by `install()` is in use.
+    pub fn is_installed() -> bool { INSTALLED.load( Ordering::SeqCst )}
+
+    /// The epoll file descriptor of the current thread's notifier, which becomes readable
+    /// when Tcl has file events to handle or has been alerted by another thread.
+    /// Returns `None` if the notifier is not installed or not initialized yet.
+    pub fn as_raw_fd() -> Option<RawFd> {
+        NOTIFIER.with( |notifier| notifier.borrow().as_ref().map( |notifier| notifier.epfd ))
+    }
+
+    /// The longest time the foreign event loop may block before calling `process_events()`,
+    /// e.g. for firing Tcl timers. Returns `None` if Tcl has no time-based work.
+    pub fn timeout() -> Option<Duration> {
+        NOTIFIER.with( |notifier| notifier
+            .borrow()
+            .as_ref()
+            .and_then( |notifier| notifier.deadline )
+            .map( |deadline| deadline.saturating_duration_since( Instant::now() )))
+    }
+
+    /// Handles all pending Tcl events without blocking, and updates `timeout()`.
+    /// Returns the number of events handled.
+    pub fn process_events() -> usize {
+        let mut count = 0;
+        let flags = clib::TCL_ALL_EVENTS as c_int;
+
+        while unsafe{ clib::Tcl_DoOneEvent( flags | clib::TCL_DONT_WAIT as c_int )} != 0 {
+            count += 1;
+        }
+
+        // A non-waiting `Tcl_DoOneEvent()` always asks the notifier for zero block time.
+        // Run a waiting one in probing mode to learn when Tcl's next timer is due.
+        let probing = NOTIFIER.with( |notifier| notifier
+            .borrow_mut()
+            .as_mut()
+            .map( |notifier| notifier.probing = true )
+            .is_some()
+        );
+
+        if probing {
+            unsafe{ clib::Tcl_DoOneEvent( flags ); }
+            NOTIFIER.with( |notifier| notifier
+                .borrow_mut()
+                .as_mut()
+                .map( |notifier| notifier.probing = false ));
+        }
+
+        count
+    }
+
+    fn epoll_events( mask: c_int ) -> u32 {
+        let mut events = 0;
+        if mask & READABLE  != 0 { events |= libc::EPOLLIN  as u32; }
+        if mask & WRITABLE  != 0 { events |= libc::EPOLLOUT as u32; }
+        if mask & EXCEPTION != 0 { events |= libc::EPOLLPRI as u32; }
+        events
+    }
+
+    fn tcl_mask( events: u32 ) -> c_int {
+        let mut mask = 0;
+        if events & ( libc::EPOLLIN | libc::EPOLLHUP | libc::EPOLLERR ) as u32 != 0 { mask |= READABLE;  }
+        if events & libc::EPOLLOUT as u32 != 0 { mask |= WRITABLE;  }
+        if events & libc::EPOLLPRI as u32 != 0 { mask |= EXCEPTION; }
+        mask
+    }
+
+    fn deadline_from( time_ptr: *const clib::Tcl_Time ) -> Option<Instant> {
+        unsafe{ time_ptr.as_ref() }.map( |time| Instant::now()
+            + Duration::from_secs( time.sec.max(0) as u64 )
+            + Duration::from_micros( time.usec.max(0) as u64 ))
+    }
+
+    unsafe extern "C" fn init_notifier() -> clib::ClientData {
+        let epfd = libc::epoll_create1( libc::EPOLL_CLOEXEC );
+        let wake = libc::eventfd( 0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK );
+        if epfd < 0 || wake < 0 {
+            tcl_panic!( "tcl::notifier: failed to create epoll or eventfd." );
+        }
+
+        let mut event = libc::epoll_event{ events: libc::EPOLLIN as u32, u64: WAKE_TOKEN };
+        libc::epoll_ctl( epfd, libc::EPOLL_CTL_ADD, wake, &mut event );
+
+        NOTIFIER.with( |notifier| {
+            *notifier.borrow_mut() = Some( Notifier {
+                epfd,
+                wake,
+                handlers : HashMap::new(),
+                deadline : None,
+                probing  : false,
+            });
+        });
+
+        wake as isize as clib::ClientData
+    }
+
+    unsafe extern "C" fn finalize_notifier( _client_data: clib::ClientData ) {
+        if let Some( notifier ) = NOTIFIER.with( |notifier| notifier.borrow_mut().take() ) {
+            libc::close( notifier.wake );
+            libc::close( notifier.epfd );
+        }
+    }
+
+    unsafe extern "C" fn alert_notifier( client_data: clib::ClientData ) {
+        let wake = client_data as isize as RawFd;
+        let one = 1_u64;
+        libc::write( wake, &one as *const u64 as *const c_void, mem::size_of::<u64>() );
+    }
+
+    unsafe extern "C" fn service_mode_hook( _mode: c_int ) {}
+
+    unsafe extern "C" fn set_timer( time_ptr: *const clib::Tcl_Time ) {
+        let deadline = deadline_from( time_ptr );
+        NOTIFIER.with( |notifier| {
+            if let Some( notifier ) = notifier.borrow_mut().as_mut() {
+                notifier.deadline = deadline;
+            }
+        });
+    }
+
+    unsafe extern "C" fn create_file_handler( fd: c_int, mask: c_int, proc_: clib::Tcl_FileProc, client_data: clib::ClientData ) {
+        NOTIFIER.with( |notifier| {
+            if let Some( notifier ) = notifier.borrow_mut().as_mut() {
+                let op = if notifier.handlers.contains_key( &fd ) { libc::EPOLL_CTL_MOD } else { libc::EPOLL_CTL_ADD };
+                let mut event = libc::epoll_event{ events: epoll_events( mask ), u64: fd as u64 };
+                libc::epoll_ctl( notifier.epfd, op, fd, &mut event );
+                notifier.handlers.insert( fd, Handler{ mask, ready: 0, proc_, client_data });
+            }
+        });
+    }
+
+    unsafe extern "C" fn delete_file_handler( fd: c_int ) {
+        NOTIFIER.with( |notifier| {
+            if let Some( notifier ) = notifier.borrow_mut().as_mut() {
+                if notifier.handlers.remove( &fd ).is_some() {
+                    libc::epoll_ctl( notifier.epfd, libc::EPOLL_CTL_DEL, fd, ptr::null_mut() );
+                }
+            }
+        });
+    }
+
+    unsafe extern "C" fn wait_for_event( time_ptr: *const clib::Tcl_Time ) -> c_int {
+        let mut events: [libc::epoll_event; MAX_EVENTS] = mem::zeroed();
+
+        // `None` if the notifier has not been initialized. Otherwise there is always something
+        // to wait for, the wake eventfd, which `Tcl_AlertNotifier()` writes from other threads.
+        let waited = NOTIFIER.with( |notifier| {
+            let mut notifier = notifier.borrow_mut();
+            let notifier = notifier.as_mut()?;
+
+            notifier.deadline = deadline_from( time_ptr );
+
+            let timeout_ms = if notifier.probing {
+                0
+            } else {
+                match time_ptr.as_ref() {
+                    Some( time ) => c_int::try_from( time.sec ).unwrap_or( c_int::MAX )
+                        .saturating_mul( 1000 )
+                        .saturating_add(( time.usec as c_int + 999 ) / 1000 ),
+                    None => -1,
+                }
+            };
+
+            let count = libc::epoll_wait( notifier.epfd, events.as_mut_ptr(), MAX_EVENTS as c_int, timeout_ms );
+            // errno is read right away, before anything else can overwrite it.
+            Some( if count >= 0 { Ok( count as usize )} else { Err( std::io::Error::last_os_error() )})
+        });
+
+        let count = match waited {
+            Some( Ok( count )) => count,
+            Some( Err( err )) => return if err.kind() == std::io::ErrorKind::Interrupted { 0 } else { -1 },
+            None => return -1,
+        };
+
+        let mut to_queue = Vec::new();
+
+        NOTIFIER.with( |notifier| {
+            if let Some( notifier ) = notifier.borrow_mut().as_mut() {
+                for event in &events[..count] {
+                    if event.u64 == WAKE_TOKEN {
+                        let mut counter = 0_u64;
+                        libc::read( notifier.wake, &mut counter as *mut u64 as *mut c_void, mem::size_of::<u64>() );
+                    } else {
+                        let fd = event.u64 as RawFd;
+                        if let Some( handler ) = notifier.handlers.get_mut( &fd ) {
+                            if handler.ready == 0 {
+                                to_queue.push( fd );
+                            }
+                            handler.ready |= tcl_mask( event.events );
+                        }
+                    }
+                }
+            }
+        });
+
+        for fd in to_queue {
+            let file_event = clib::Tcl_Alloc( mem::size_of::<FileEvent>() as u32 ) as *mut FileEvent;
+            (*file_event).header.proc_ = Some( file_event_proc );
+            (*file_event).header.nextPtr = ptr::null_mut();
+            (*file_event).fd = fd;
+            clib::Tcl_QueueEvent( file_event as *mut clib::Tcl_Event, clib::Tcl_QueuePosition_TCL_QUEUE_TAIL );
+        }
+
+        let probing = NOTIFIER.with( |notifier| notifier.borrow().as_ref().map( |notifier| notifier.probing ).unwrap_or( false ));
+        if probing { 1 } else { 0 }
+    }
+
+    unsafe extern "C" fn file_event_proc( event: *mut clib::Tcl_Event, flags: c_int ) -> c_int {
+        if flags & clib::TCL_FILE_EVENTS as c_int == 0 {
+            return 0;
+        }
+
+        let fd = (*( event as *mut FileEvent )).fd;
+        let handler = NOTIFIER.with( |notifier| notifier
+            .borrow_mut()
+            .as_mut()
+            .and_then( |notifier| notifier.handlers.get_mut( &fd ))
+            .map( |handler| {
+                let mask = handler.ready & handler.mask;
+                handler.ready = 0;
+                ( handler.proc_, handler.client_data, mask )
+            })
+        );
+
+        if let Some(( Some( proc_ ), client_data, mask )) = handler {
+            if mask != 0 {
+                proc_( client_data, mask );
+            }
+        }
+
+        1
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    use mio::{Events, Interest, Poll, Token, unix::pipe};
+
+    use std::{
+        cell::Cell,
+        io::{Read, Write},
+        os::unix::io::AsRawFd,
+        rc::Rc,
+        time::Duration,
+    };
+
+    #[test]
+    fn tcl_timers_and_mio_sources() {
+        let interpreter = Interpreter::new().unwrap();
+
+        let mut poll = Poll::new().unwrap();
+        let (mut sender, mut receiver) = pipe::new().unwrap();
+        poll.registry().register( &mut receiver, Token(0), Interest::READABLE ).unwrap();
+
+        let received = Rc::new( Cell::new( false ));
+        let received_ = received.clone();
+        let _handler = FileHandler::new( poll.as_raw_fd(), READABLE, move |_mask| {
+            let mut events = Events::with_capacity( 8 );
+            poll.poll( &mut events, Some( Duration::ZERO )).unwrap();
+            for event in events.iter() {
+                if event.token() == Token(0) {
+                    let mut buf = [0_u8; 16];
+                    while let Ok( n ) = receiver.read( &mut buf ) {
+                        if n == 0 { break; }
+                        received_.set( true );
+                    }
+                }
+            }
+        });
+
+        interpreter.run( "after 10 { set ::timer_fired 1 }" ).unwrap();
+        interpreter.run( "after 5000 { set ::gave_up 1 }" ).unwrap();
+        sender.write_all( b"ping" ).unwrap();
+
+        while !( received.get() && interpreter.get( "timer_fired" ).is_ok() ) {
+            assert!( interpreter.get( "gave_up" ).is_err() );
+            unsafe{ clib::Tcl_DoOneEvent( 0 ); }
+        }
+    }
+}
@@ -52,8 +52,9 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     ffi::CString,
+    cmp::Ordering,
     fmt::{self, Debug},
-    hash::Hash,
+    hash::{Hash, Hasher},
     mem,
     ops::Range,
     os::raw::{c_char, c_double, c_int, c_long, c_longlong, c_void},
@@ -184,6 +185,27 @@ impl Obj {
         unsafe{ self.0.as_ref().refCount > 1 }
     }
 
+    /// Makes the obj unshared, so that it can be modified in place without affecting other
+    /// references to the same value. A shared obj is replaced by a duplicate of it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcl::*;
+    ///
+    /// let original = Obj::from(( "a", "b" ));
+    /// let mut copy = original.clone();
+    /// copy.make_mut().list_append_element( "c" ).unwrap();
+    /// assert_eq!( original.to_string(), "a b" );
+    /// assert_eq!( copy.to_string(), "a b c" );
+    /// ```
+    pub fn make_mut( &mut self ) -> &mut Obj {
+        if self.is_shared() {
+            *self = unsafe{ Obj::from_raw( clib::Tcl_DuplicateObj( self.as_ptr() ))};
+        }
+        self
+    }
+
     /// Clones the underlying value of this obj.
     pub fn clone_value( &self ) -> Option<Obj> {
         unsafe{ *self.type_ptr() }
@@ -195,6 +217,48 @@ impl Obj {
             })
     }
 
+    /// Returns the name of the obj's internal representation type, e.g. "int", "list", "dict"
+    /// or "bytecode", or `None` if the obj has only a string representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tcl::*;
+    ///
+    /// let interp = Interpreter::new().unwrap();
+    /// let obj = Obj::from( "1 2 3" );
+    /// assert_eq!( obj.type_name(), None );
+    /// interp.eval(( "llength", obj.clone() )).unwrap();
+    /// assert_eq!( obj.type_name(), Some( "list" ));
+    /// ```
+    pub fn type_name( &self ) -> Option<&'static str> {
+        NonNull::new( self.type_ptr() as *mut clib::Tcl_ObjType )
+            .and_then( |ptr| unsafe{ std::ffi::CStr::from_ptr( ptr.as_ref().name )}.to_str().ok() )
+    }
+
+    /// Returns the reference count of the obj, including the one held by `self`.
+    pub fn ref_count( &self ) -> usize {
+        unsafe{ self.0.as_ref().refCount as usize }
+    }
+
+    // The MUTF-8 bytes of the obj's string representation, generated if necessary.
+    fn string_bytes( &self ) -> &[u8] {
+        let mut len: c_int = 0;
+        unsafe {
+            let data = clib::Tcl_GetStringFromObj( self.as_ptr(), &mut len ) as *const u8;
+            slice::from_raw_parts( data, len as usize )
+        }
+    }
+
+    // The value of the obj as a real number, parsed without changing its internal representation.
+    fn real_value( &self ) -> Option<f64> {
+        let mut value: c_double = 0.0;
+        let code = unsafe {
+            clib::Tcl_GetDouble( null_mut(), self.string_bytes().as_ptr() as *const c_char, &mut value )
+        };
+        if code == clib::TCL_OK as c_int { Some( value )} else { None }
+    }
+
     pub(crate) fn type_ptr( &self ) -> *const clib::Tcl_ObjType {
         unsafe{ self.0.as_ref().typePtr }
     }
@@ -218,6 +282,111 @@ impl fmt::Display for Obj {
     }
 }
 
+/// Objs are equal if their string representations are equal, as in Tcl's `eq` operator.
+impl PartialEq for Obj {
+    fn eq( &self, other: &Obj ) -> bool {
+        self.string_bytes() == other.string_bytes()
+    }
+}
+
+impl Eq for Obj {}
+
+impl Hash for Obj {
+    fn hash<H: Hasher>( &self, state: &mut H ) {
+        self.string_bytes().hash( state );
+    }
+}
+
+impl PartialOrd for Obj {
+    fn partial_cmp( &self, other: &Obj ) -> Option<Ordering> { Some( self.cmp( other ))}
+}
+
+/// Numbers sort before other values and are compared by value, as in `lsort -real`. Other
+/// values are compared as in `lsort -dictionary`. Ties are broken by comparing the string
+/// representations byte by byte, keeping the order consistent with equality.
+///
+/// # Example
+///
+/// ```
+/// use tcl::*;
+///
+/// let mut objs = vec![ Obj::from("x10"), Obj::from("x9"), Obj::from("1e1"), Obj::from("0x2"), Obj::from("X9") ];
+/// objs.sort();
+/// assert_eq!( objs.iter().map( |obj| obj.to_string() ).collect::<Vec<_>>(),
+///     vec![ "0x2", "1e1", "X9", "x9", "x10" ]);
+/// ```
+impl Ord for Obj {
+    fn cmp( &self, other: &Obj ) -> Ordering {
+        let by_value = match ( self.real_value(), other.real_value() ) {
+            ( Some( left ), Some( right )) => left.total_cmp( &right ),
+            ( Some( _ ), None ) => Ordering::Less,
+            ( None, Some( _ )) => Ordering::Greater,
+            ( None, None ) => Ordering::Equal,
+        };
+        by_value
+            .then_with( || dictionary_cmp( &self.get_string(), &other.get_string() ))
+            .then_with( || self.string_bytes().cmp( other.string_bytes() ))
+    }
+}
+
+// Compares strings as `lsort -dictionary` does: case-insensitively, with embedded decimal numbers
+// compared as integers. Differences in case and leading zeros only count if nothing else differs.
+fn dictionary_cmp( left: &str, right: &str ) -> Ordering {
+    let mut left = left.chars().peekable();
+    let mut right = right.chars().peekable();
+    let mut secondary = Ordering::Equal;
+
+    loop {
+        match ( left.peek().copied(), right.peek().copied() ) {
+            ( Some( l ), Some( r )) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let mut zeros = 0_isize;
+                while left.peek() == Some( &'0' ) && left.clone().nth(1).is_some_and( |c| c.is_ascii_digit() ) {
+                    left.next();
+                    zeros += 1;
+                }
+                while right.peek() == Some( &'0' ) && right.clone().nth(1).is_some_and( |c| c.is_ascii_digit() ) {
+                    right.next();
+                    zeros -= 1;
+                }
+                if secondary == Ordering::Equal {
+                    secondary = zeros.cmp( &0 );
+                }
+
+                let mut digits = Ordering::Equal;
+                loop {
+                    let l = left.next_if( char::is_ascii_digit );
+                    let r = right.next_if( char::is_ascii_digit );
+                    match ( l, r ) {
+                        ( Some( l ), Some( r )) => digits = digits.then( l.cmp( &r )),
+                        ( Some( _ ), None ) => return Ordering::Greater,
+                        ( None, Some( _ )) => return Ordering::Less,
+                        ( None, None ) => break,
+                    }
+                }
+                if digits != Ordering::Equal {
+                    return digits;
+                }
+            },
+            ( Some( l ), Some( r )) => {
+                left.next();
+                right.next();
+                let lower = |c: char| c.to_lowercase().next().unwrap_or( c );
+                match lower( l ).cmp( &lower( r )) {
+                    Ordering::Equal => if secondary == Ordering::Equal {
+                        if l.is_uppercase() && r.is_lowercase() {
+                            secondary = Ordering::Less;
+                        } else if r.is_uppercase() && l.is_lowercase() {
+                            secondary = Ordering::Greater;
+                        }
+                    },
+                    diff => return diff,
+                }
+            },
+            ( l, r ) => return l.is_some().cmp( &r.is_some() ).then( secondary ),
+        }
+    }
+}
+
 impl From<bool> for Obj {
     fn from( b: bool ) -> Obj {
         crate::init();
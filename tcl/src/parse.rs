@@ -0,0 +1,357 @@
+//! Tcl script parser, for inspecting scripts without evaluating them.
+//!
+//! Scripts are split into commands, commands into words, and words into tokens, all with
+//! byte ranges in the source script.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::parse::{self, TokenKind};
+//!
+//! let script = "set greeting \"hello $name\" ;# say hi\nputs [string toupper $greeting]";
+//! let commands = parse::parse( script ).unwrap();
+//!
+//! assert_eq!( commands.len(), 2 );
+//! assert_eq!( commands[0].name( script ), Some( "set" ));
+//! assert_eq!( commands[1].words[1].kind, TokenKind::Word );
+//!
+//! let greeting = &commands[0].words[2];
+//! assert_eq!( greeting.as_str( script ), "\"hello $name\"" );
+//! assert_eq!( greeting.components[1].kind, TokenKind::Variable );
+//!
+//! let nested = commands[1].words[1].components[0].nested_commands( script ).unwrap();
+//! assert_eq!( nested[0].name( script ), Some( "string" ));
+//! assert_eq!( nested[0].words[2].as_str( script ), "$greeting" );
+//!
+//! assert!( parse::parse( "puts {unbalanced" ).unwrap_err().incomplete );
+//! ```
+
+use crate::{
+    error::ParseError,
+    interp,
+};
+
+use mutf8::mstr;
+
+use std::{
+    mem,
+    ops::Range,
+    os::raw::{c_char, c_int},
+};
+
+/// Kinds of tokens.
+#[derive( Clone, Copy, Debug, PartialEq, Eq )]
+pub enum TokenKind {
+    /// A word containing substitutions. Its components are the parts of the word.
+    Word,
+    /// A word without substitutions. Its only component is a `Text` token.
+    SimpleWord,
+    /// A word prefixed by `{*}`. Its components are the parts of the word.
+    ExpandWord,
+    /// Literal text.
+    Text,
+    /// A backslash sequence.
+    Backslash,
+    /// A command substitution in brackets.
+    Command,
+    /// A variable substitution. Its first component is a `Text` token of the variable name,
+    /// and the remaining components, if any, form the array element name.
+    Variable,
+    /// A subexpression, only in expressions.
+    SubExpr,
+    /// An operator, only in expressions.
+    Operator,
+}
+
+impl TokenKind {
+    fn from_c_int( kind: c_int ) -> Self {
+        match kind as u32 {
+            clib::TCL_TOKEN_WORD        => TokenKind::Word,
+            clib::TCL_TOKEN_SIMPLE_WORD => TokenKind::SimpleWord,
+            clib::TCL_TOKEN_EXPAND_WORD => TokenKind::ExpandWord,
+            clib::TCL_TOKEN_BS          => TokenKind::Backslash,
+            clib::TCL_TOKEN_COMMAND     => TokenKind::Command,
+            clib::TCL_TOKEN_VARIABLE    => TokenKind::Variable,
+            clib::TCL_TOKEN_SUB_EXPR    => TokenKind::SubExpr,
+            clib::TCL_TOKEN_OPERATOR    => TokenKind::Operator,
+            _                           => TokenKind::Text,
+        }
+    }
+}
+
+/// A token and its components.
+#[derive( Clone, Debug, PartialEq, Eq )]
+pub struct Token {
+    /// The kind of the token.
+    pub kind       : TokenKind,
+    /// Byte range of the token in the script.
+    pub range      : Range<usize>,
+    /// The tokens this token is made of.
+    pub components : Vec<Token>,
+}
+
+impl Token {
+    /// The source text of the token.
+    pub fn as_str<'s>( &self, script: &'s str ) -> &'s str { &script[ self.range.clone() ]}
+
+    /// Returns the literal value of a `SimpleWord` or `Text` token, or `None` for other kinds,
+    /// whose values are only known after substitutions.
+    pub fn literal<'s>( &self, script: &'s str ) -> Option<&'s str> {
+        match self.kind {
+            TokenKind::SimpleWord => self.components.first().map( |text| text.as_str( script )),
+            TokenKind::Text       => Some( self.as_str( script )),
+            _                     => None,
+        }
+    }
+
+    /// Parses the script inside the brackets of a `Command` token. Returns an empty list
+    /// for other kinds of tokens.
+    pub fn nested_commands( &self, script: &str ) -> Result<Vec<Command>, ParseError> {
+        if self.kind != TokenKind::Command || self.range.len() < 2 {
+            return Ok( Vec::new() );
+        }
+        let start = self.range.start + 1;
+        let mut commands = parse( &script[ start..self.range.end-1 ]).map_err( |mut err| {
+            err.offset += start;
+            err
+        })?;
+        commands.iter_mut().for_each( |command| command.shift( start ));
+        Ok( commands )
+    }
+
+    fn shift( &mut self, delta: usize ) {
+        self.range = self.range.start+delta .. self.range.end+delta;
+        self.components.iter_mut().for_each( |token| token.shift( delta ));
+    }
+}
+
+/// A command, with its comment if any.
+#[derive( Clone, Debug, PartialEq, Eq )]
+pub struct Command {
+    /// Byte range of the comments preceding the command, if any.
+    pub comment : Option<Range<usize>>,
+    /// Byte range of the command, including its terminating newline or semicolon.
+    pub range   : Range<usize>,
+    /// The words of the command, each of which is a `Word`, `SimpleWord` or `ExpandWord` token.
+    pub words   : Vec<Token>,
+}
+
+impl Command {
+    /// The command name, if the first word is literal.
+    pub fn name<'s>( &self, script: &'s str ) -> Option<&'s str> {
+        self.words.first().and_then( |word| word.literal( script ))
+    }
+
+    fn shift( &mut self, delta: usize ) {
+        self.comment = self.comment.take().map( |comment| comment.start+delta .. comment.end+delta );
+        self.range = self.range.start+delta .. self.range.end+delta;
+        self.words.iter_mut().for_each( |token| token.shift( delta ));
+    }
+}
+
+// Tcl parses its internal modified UTF-8, which differs from UTF-8 in encoding NUL and
+// characters beyond the BMP. Maps offsets in it back to offsets in the Rust string.
+struct Source<'s> {
+    mutf8      : std::borrow::Cow<'s, mstr>,
+    // (offset in modified UTF-8, offset in UTF-8) of each character boundary.
+    boundaries : Option<Vec<(usize, usize)>>,
+}
+
+impl<'s> Source<'s> {
+    fn new( script: &'s str ) -> Self {
+        let mutf8 = mstr::from_utf8( script.as_bytes() );
+        let boundaries = if mutf8.len() == script.len() {
+            None
+        } else {
+            let mut boundaries = Vec::with_capacity( script.len() + 1 );
+            let mut offset = 0;
+            for (utf8_offset, c) in script.char_indices() {
+                boundaries.push(( offset, utf8_offset ));
+                offset += match c as u32 {
+                    0            => 2,
+                    0x10000..    => 6,
+                    _            => c.len_utf8(),
+                };
+            }
+            boundaries.push(( offset, script.len() ));
+            Some( boundaries )
+        };
+        Source{ mutf8, boundaries }
+    }
+
+    fn as_ptr( &self ) -> *const c_char { self.mutf8.as_bytes().as_ptr() as *const c_char }
+
+    fn len( &self ) -> usize { self.mutf8.len() }
+
+    fn offset_of( &self, ptr: *const c_char ) -> usize {
+        let offset = ( ptr as usize ).saturating_sub( self.as_ptr() as usize ).min( self.len() );
+        match &self.boundaries {
+            None => offset,
+            Some( boundaries ) => {
+                let i = boundaries.partition_point( |&(mutf8_offset, _)| mutf8_offset <= offset );
+                boundaries[ i.saturating_sub(1) ].1
+            },
+        }
+    }
+
+    fn range_of( &self, start: *const c_char, size: c_int ) -> Range<usize> {
+        let start_offset = self.offset_of( start );
+        let end_offset = self.offset_of( unsafe{ start.add( size.max(0) as usize )});
+        start_offset..end_offset
+    }
+
+    fn tokens( &self, parse: &clib::Tcl_Parse ) -> Vec<Token> {
+        let raw = unsafe{ std::slice::from_raw_parts( parse.tokenPtr, parse.numTokens.max(0) as usize )};
+        let mut index = 0;
+        let mut tokens = Vec::new();
+        while index < raw.len() {
+            tokens.push( self.token( raw, &mut index ));
+        }
+        tokens
+    }
+
+    fn token( &self, raw: &[clib::Tcl_Token], index: &mut usize ) -> Token {
+        let token = &raw[ *index ];
+        *index += 1;
+        let end = ( *index + token.numComponents.max(0) as usize ).min( raw.len() );
+        let mut components = Vec::new();
+        while *index < end {
+            components.push( self.token( raw, index ));
+        }
+        Token {
+            kind  : TokenKind::from_c_int( token.type_ ),
+            range : self.range_of( token.start, token.size ),
+            components,
+        }
+    }
+
+    fn error( &self, parse: &clib::Tcl_Parse, interp: &crate::Interp ) -> ParseError {
+        ParseError {
+            message    : interp.result().get_string(),
+            offset     : self.offset_of( parse.term ),
+            incomplete : parse.incomplete != 0,
+        }
+    }
+}
+
+/// Iterator over the commands of a script, created by `commands()`.
+/// It stops after the first error.
+pub struct Commands<'s> {
+    source : Source<'s>,
+    next   : usize,
+    failed : bool,
+}
+
+impl<'s> Iterator for Commands<'s> {
+    type Item = Result<Command, ParseError>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        while !self.failed && self.next < self.source.len() {
+            let mut parse = Box::new( unsafe{ mem::zeroed::<clib::Tcl_Parse>() });
+            let result = interp::with_scratch( |interp| unsafe {
+                let start = self.source.as_ptr().add( self.next );
+                let code = clib::Tcl_ParseCommand( interp.as_ptr(), start, ( self.source.len() - self.next ) as c_int, 0, &mut *parse );
+                if code != clib::TCL_OK as c_int {
+                    let error = self.source.error( &parse, interp );
+                    clib::Tcl_FreeParse( &mut *parse );
+                    return Err( error );
+                }
+
+                let command = Command {
+                    comment : if parse.commentSize > 0 { Some( self.source.range_of( parse.commentStart, parse.commentSize ))} else { None },
+                    range   : self.source.range_of( parse.commandStart, parse.commandSize ),
+                    words   : self.source.tokens( &parse ),
+                };
+
+                let consumed = parse.commandStart.add( parse.commandSize as usize ).offset_from( start ) as usize;
+                clib::Tcl_FreeParse( &mut *parse );
+                Ok(( command, consumed ))
+            });
+
+            match result {
+                Ok(( command, consumed )) => {
+                    self.next += consumed.max(1);
+                    if !command.words.is_empty() || command.comment.is_some() {
+                        return Some( Ok( command ));
+                    }
+                },
+                Err( error ) => {
+                    self.failed = true;
+                    return Some( Err( error ));
+                },
+            }
+        }
+        None
+    }
+}
+
+/// Iterates over the commands of `script`.
+pub fn commands( script: &str ) -> Commands<'_> {
+    crate::init();
+    Commands{ source: Source::new( script ), next: 0, failed: false }
+}
+
+/// Parses all commands of `script`.
+pub fn parse( script: &str ) -> Result<Vec<Command>, ParseError> {
+    commands( script ).collect()
+}
+
+/// Parses the variable reference at the beginning of `text`, e.g. `$name`, `${name}` or
+/// `$arr(elem)`. Returns a `Variable` token, or a `Text` token of `$` if no variable name
+/// follows it.
+pub fn parse_var_name( text: &str ) -> Result<Token, ParseError> {
+    crate::init();
+    let source = Source::new( text );
+    let mut parse = Box::new( unsafe{ mem::zeroed::<clib::Tcl_Parse>() });
+
+    interp::with_scratch( |interp| unsafe {
+        let code = clib::Tcl_ParseVarName( interp.as_ptr(), source.as_ptr(), source.len() as c_int, &mut *parse, 0 );
+        let result = if code != clib::TCL_OK as c_int {
+            Err( source.error( &parse, interp ))
+        } else {
+            source.tokens( &parse ).into_iter().next().ok_or_else( || ParseError {
+                message    : "missing variable reference".to_owned(),
+                offset     : 0,
+                incomplete : false,
+            })
+        };
+        clib::Tcl_FreeParse( &mut *parse );
+        result
+    })
+}
+
+/// Checks if `script` contains no incomplete command, e.g. one with unbalanced braces.
+pub fn is_complete( script: &str ) -> bool {
+    crate::init();
+    let source = Source::new( script );
+    let script = std::ffi::CString::new( source.mutf8.as_bytes() ).unwrap_or_default();
+    unsafe{ clib::Tcl_CommandComplete( script.as_ptr() ) != 0 }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_and_expansion() {
+        let script = "puts \"\u{1f600}\" {*}$args(x)\\n;\n# done\n";
+        let commands = parse( script ).unwrap();
+        assert_eq!( commands.len(), 2 );
+
+        let words = &commands[0].words;
+        assert_eq!( words[1].as_str( script ), "\"\u{1f600}\"" );
+        assert_eq!( words[2].kind, TokenKind::ExpandWord );
+
+        let var = &words[2].components[0];
+        assert_eq!( var.kind, TokenKind::Variable );
+        assert_eq!( var.as_str( script ), "$args(x)" );
+        assert_eq!( words[2].components[1].kind, TokenKind::Backslash );
+
+        assert!( commands[1].words.is_empty() );
+        assert_eq!( &script[ commands[1].comment.clone().unwrap() ], "# done\n" );
+
+        let var = parse_var_name( "${a b}c" ).unwrap();
+        assert_eq!( var.range, 0..6 );
+        assert!( !is_complete( "if {1} {" ));
+    }
+}
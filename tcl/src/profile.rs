@@ -0,0 +1,327 @@
+//! Command execution profiler built on `Tcl_CreateObjTrace()`.
+//!
+//! Tcl calls the trace procedure right before a command executes, but never after it
+//! returns. A command is considered finished when the next command at the same or a
+//! shallower nesting level starts, or when the profiler stops. As a result, the time
+//! spent between the last command of a script and the next traced command is charged
+//! to that last command.
+
+use crate::{
+    Obj,
+    interp::Interp,
+};
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+    fmt::Write,
+    mem,
+    os::raw::{c_char, c_int},
+    ptr,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Statistics of one command, or one Tcl proc.
+#[derive( Clone, Debug, Default )]
+pub struct CommandStats {
+    /// The command name, i.e. the first word of the command.
+    pub name      : String,
+    /// How many times the command has been called.
+    pub calls     : u64,
+    /// Time spent in the command, including the commands it called.
+    pub total     : Duration,
+    /// Time spent in the command, excluding the commands it called.
+    pub self_time : Duration,
+}
+
+/// The result of profiling, returned by `Interp::profile()` or `Profiler::stop()`.
+#[derive( Clone, Debug, Default )]
+pub struct ProfileReport {
+    /// Statistics of all commands, sorted by total time in descending order.
+    pub commands : Vec<CommandStats>,
+    /// Statistics of the commands which are Tcl procs, sorted by total time in descending order.
+    pub procs    : Vec<CommandStats>,
+    /// Self time of each distinct call stack, with frames separated by `;`.
+    pub stacks   : Vec<(String, Duration)>,
+}
+
+impl ProfileReport {
+    /// Returns the statistics of the command named `name`, if it has been called.
+    pub fn command( &self, name: &str ) -> Option<&CommandStats> {
+        self.commands.iter().find( |stats| stats.name == name )
+    }
+
+    /// Returns the statistics of the Tcl proc named `name`, if it has been called.
+    pub fn proc( &self, name: &str ) -> Option<&CommandStats> {
+        self.procs.iter().find( |stats| stats.name == name )
+    }
+
+    /// Exports the call stacks in the folded format consumed by flamegraph tools, e.g.
+    /// `inferno-flamegraph` or `flamegraph.pl`. Each line is a stack followed by its self
+    /// time in microseconds.
+    pub fn to_folded( &self ) -> String {
+        let mut folded = String::new();
+        for (stack, self_time) in &self.stacks {
+            writeln!( folded, "{} {}", stack, self_time.as_micros() ).ok();
+        }
+        folded
+    }
+}
+
+struct Frame {
+    name    : String,
+    level   : c_int,
+    is_proc : bool,
+    start   : Instant,
+    child   : Duration,
+}
+
+#[derive( Default )]
+struct Records {
+    stack    : Vec<Frame>,
+    commands : HashMap<String, CommandStats>,
+    procs    : HashMap<String, CommandStats>,
+    stacks   : HashMap<String, Duration>,
+}
+
+impl Records {
+    fn finish_frames( &mut self, level: c_int, now: Instant ) {
+        while self.stack.last().map( |frame| frame.level >= level ).unwrap_or( false ) {
+            let frame = self.stack.pop().unwrap();
+            let total = now.saturating_duration_since( frame.start );
+            let self_time = total.saturating_sub( frame.child );
+
+            if let Some( parent ) = self.stack.last_mut() {
+                parent.child += total;
+            }
+
+            let mut stack = self.stack.iter().fold( String::new(), |acc, frame| acc + &frame.name + ";" );
+            stack.push_str( &frame.name );
+            *self.stacks.entry( stack ).or_default() += self_time;
+
+            let accumulate = |map: &mut HashMap<String, CommandStats>| {
+                let stats = map.entry( frame.name.clone() ).or_insert_with( || CommandStats{
+                    name: frame.name.clone(), ..Default::default()
+                });
+                stats.calls += 1;
+                stats.total += total;
+                stats.self_time += self_time;
+            };
+
+            accumulate( &mut self.commands );
+            if frame.is_proc {
+                accumulate( &mut self.procs );
+            }
+        }
+    }
+
+    fn report( &self ) -> ProfileReport {
+        fn sorted( map: &HashMap<String, CommandStats> ) -> Vec<CommandStats> {
+            let mut stats = map.values().cloned().collect::<Vec<_>>();
+            stats.sort_by( |a,b| b.total.cmp( &a.total ).then_with( || a.name.cmp( &b.name )));
+            stats
+        }
+
+        let mut stacks = self.stacks.iter()
+            .map( |(stack, self_time)| (stack.clone(), *self_time) )
+            .collect::<Vec<_>>();
+        stacks.sort();
+
+        ProfileReport {
+            commands : sorted( &self.commands ),
+            procs    : sorted( &self.procs ),
+            stacks   ,
+        }
+    }
+}
+
+struct State {
+    enabled   : Cell<bool>,
+    proc_addr : Option<usize>,
+    records   : RefCell<Records>,
+}
+
+/// A running profiler, which records every command executed in the interpreter.
+/// The trace is removed when the profiler is stopped or dropped.
+pub struct Profiler {
+    interp : Interp,
+    trace  : clib::Tcl_Trace,
+    state  : Rc<State>,
+}
+
+impl Profiler {
+    /// Resumes recording, if paused.
+    pub fn resume( &self ) { self.state.enabled.set( true ); }
+
+    /// Pauses recording. Commands started before pausing are still timed.
+    pub fn pause( &self ) { self.state.enabled.set( false ); }
+
+    /// Checks if the profiler is recording.
+    pub fn is_recording( &self ) -> bool { self.state.enabled.get() }
+
+    /// Returns the statistics of the commands which have finished so far.
+    pub fn report( &self ) -> ProfileReport {
+        self.state.records.borrow().report()
+    }
+
+    /// Stops profiling and returns the statistics of all recorded commands.
+    pub fn stop( mut self ) -> ProfileReport {
+        self.remove_trace();
+        let mut records = self.state.records.borrow_mut();
+        records.finish_frames( c_int::MIN, Instant::now() );
+        records.report()
+    }
+
+    fn remove_trace( &mut self ) {
+        if !self.trace.is_null() {
+            unsafe{ clib::Tcl_DeleteTrace( self.interp.as_ptr(), self.trace ); }
+            self.trace = ptr::null_mut();
+        }
+    }
+}
+
+impl Drop for Profiler {
+    fn drop( &mut self ) {
+        self.remove_trace();
+    }
+}
+
+unsafe extern "C" fn trace_proc(
+    client_data : clib::ClientData,
+    _interp     : *mut clib::Tcl_Interp,
+    level       : c_int,
+    _command    : *const c_char,
+    token       : clib::Tcl_Command,
+    objc        : c_int,
+    objv        : *const *mut clib::Tcl_Obj,
+) -> c_int {
+    let now = Instant::now();
+    let state = &*( client_data as *const State );
+    let mut records = state.records.borrow_mut();
+
+    records.finish_frames( level, now );
+
+    if state.enabled.get() && objc > 0 {
+        let name = Obj::from_raw( *objv ).get_string().replace( [';', ' '], "_" );
+
+        let mut info = mem::zeroed::<clib::Tcl_CmdInfo>();
+        let is_proc = state.proc_addr.is_some()
+            && clib::Tcl_GetCommandInfoFromToken( token, &mut info ) != 0
+            && info.objProc.map( |obj_proc| obj_proc as usize ) == state.proc_addr;
+
+        records.stack.push( Frame{ name, level, is_proc, start: Instant::now(), child: Duration::ZERO });
+    }
+
+    clib::TCL_OK as c_int
+}
+
+unsafe extern "C" fn trace_delete_proc( client_data: clib::ClientData ) {
+    drop( Rc::from_raw( client_data as *const State ));
+}
+
+const PROC_PROBE: &str = "::tcl_rs_profiler_proc_probe";
+
+thread_local! {
+    // The address found by `proc_addr()`, which is the same for all interpreters.
+    static PROC_ADDR: Cell<Option<usize>> = const { Cell::new( None )};
+}
+
+impl Interp {
+    // Every Tcl proc shares the same object-based command procedure,
+    // which is not exported by Tcl. Let's get its address from a throwaway proc,
+    // once per thread.
+    fn proc_addr( &self ) -> Option<usize> {
+        if let Some( addr ) = PROC_ADDR.with( Cell::get ) {
+            return Some( addr );
+        }
+
+        self.run(( "proc", PROC_PROBE, "", "" )).ok()?;
+
+        let name = CString::new( PROC_PROBE ).ok()?;
+        let mut info = unsafe{ mem::zeroed::<clib::Tcl_CmdInfo>() };
+        let found = unsafe{ clib::Tcl_GetCommandInfo( self.as_ptr(), name.as_ptr(), &mut info )} != 0;
+
+        self.run(( "rename", PROC_PROBE, "" )).ok();
+
+        let addr = if found {
+            info.objProc.map( |obj_proc| obj_proc as usize )
+        } else {
+            None
+        };
+        PROC_ADDR.with( |proc_addr| proc_addr.set( addr ));
+        addr
+    }
+
+    /// Starts a profiler which records call counts, total time and self time of every
+    /// command executed in this interpreter, until it is stopped or dropped.
+    ///
+    /// Note that tracing disables inline compilation of commands, which makes scripts
+    /// run slower.
+    pub fn start_profiler( &self ) -> Profiler {
+        let state = Rc::new( State {
+            enabled   : Cell::new( true ),
+            proc_addr : self.proc_addr(),
+            records   : RefCell::default(),
+        });
+
+        let trace = unsafe {
+            clib::Tcl_CreateObjTrace(
+                self.as_ptr(),
+                0,
+                0,
+                Some( trace_proc ),
+                Rc::into_raw( state.clone() ) as clib::ClientData,
+                Some( trace_delete_proc ),
+            )
+        };
+
+        Profiler{ interp: self.clone(), trace, state }
+    }
+
+    /// Profiles the commands executed in this interpreter while running `f`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    ///
+    /// let interpreter = Interpreter::new().unwrap();
+    /// interpreter.run( "proc fib {n} { expr { $n < 2 ? $n : [fib [expr {$n-1}]] + [fib [expr {$n-2}]] }}" )?;
+    ///
+    /// let report = interpreter.profile( || { interpreter.run( "fib 10" ).ok(); });
+    /// assert_eq!( report.proc("fib").unwrap().calls, 177 );
+    /// assert!( report.to_folded().starts_with( "fib " ));
+    ///
+    /// # TclResult::<()>::Ok(())
+    /// ```
+    pub fn profile( &self, f: impl FnOnce() ) -> ProfileReport {
+        let profiler = self.start_profiler();
+        f();
+        profiler.stop()
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn pause_and_resume() {
+        let interpreter = Interpreter::new().unwrap();
+        interpreter.run( "proc noop {} {}" ).unwrap();
+
+        let profiler = interpreter.start_profiler();
+        interpreter.run( "noop" ).unwrap();
+        profiler.pause();
+        assert!( !profiler.is_recording() );
+        interpreter.run( "noop" ).unwrap();
+        profiler.resume();
+        interpreter.run( "noop" ).unwrap();
+
+        let report = profiler.stop();
+        assert_eq!( report.proc("noop").unwrap().calls, 2 );
+        assert_eq!( report.command("noop").unwrap().calls, 2 );
+    }
+}
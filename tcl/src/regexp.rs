@@ -0,0 +1,378 @@
+//! Tcl's regular expression engine, with the same semantics as `regexp`, `regsub` and the
+//! text widget's `search` command.
+//!
+//! Patterns are Advanced Regular Expressions (AREs) as described in Tcl's `re_syntax`
+//! manual page. Positions are byte offsets in the Rust string being searched.
+
+use crate::{
+    Obj,
+    error::InvalidRegex,
+    interp,
+};
+
+use std::{
+    ops::{BitOr, BitOrAssign, Range},
+    os::raw::c_int,
+    ptr,
+    sync::OnceLock,
+};
+
+/// Compilation flags of a `Regex`, combined with `|`.
+#[derive( Clone, Copy, Debug, Default, PartialEq, Eq )]
+pub struct Flags( c_int );
+
+impl Flags {
+    /// Advanced regular expression syntax, without other flags.
+    pub const NONE      : Flags = Flags( 0 );
+    /// Case-insensitive matching, as `regexp -nocase`.
+    pub const NOCASE    : Flags = Flags( clib::TCL_REG_NOCASE as c_int );
+    /// Ignores white space and comments in the pattern, as `regexp -expanded`.
+    pub const EXPANDED  : Flags = Flags( clib::TCL_REG_EXPANDED as c_int );
+    /// Newline-sensitive matching, as `regexp -line`.
+    pub const LINE      : Flags = Flags( clib::TCL_REG_NEWLINE as c_int );
+    /// `[^` and `.` never match newline, as `regexp -linestop`.
+    pub const LINESTOP  : Flags = Flags( clib::TCL_REG_NLSTOP as c_int );
+    /// `^` and `$` match at the beginning and end of lines, as `regexp -lineanchor`.
+    pub const LINEANCHOR: Flags = Flags( clib::TCL_REG_NLANCH as c_int );
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+    fn bitor( self, rhs: Flags ) -> Flags { Flags( self.0 | rhs.0 )}
+}
+
+impl BitOrAssign for Flags {
+    fn bitor_assign( &mut self, rhs: Flags ) { self.0 |= rhs.0; }
+}
+
+/// A match in the text, or a matched group.
+#[derive( Clone, Copy, Debug, PartialEq, Eq )]
+pub struct Match<'t> {
+    text  : &'t str,
+    start : usize,
+    end   : usize,
+}
+
+impl<'t> Match<'t> {
+    /// Byte offset of the start of the match.
+    pub fn start( &self ) -> usize { self.start }
+
+    /// Byte offset of the end of the match.
+    pub fn end( &self ) -> usize { self.end }
+
+    /// Byte range of the match.
+    pub fn range( &self ) -> Range<usize> { self.start..self.end }
+
+    /// The matched text.
+    pub fn as_str( &self ) -> &'t str { &self.text[ self.start..self.end ]}
+}
+
+/// The whole match and the groups of one match. Group 0 is the whole match.
+#[derive( Clone, Debug )]
+pub struct Captures<'t> {
+    text   : &'t str,
+    groups : Vec<Option<(usize, usize)>>,
+}
+
+impl<'t> Captures<'t> {
+    /// Returns group `i`, or `None` if it did not participate in the match.
+    pub fn get( &self, i: usize ) -> Option<Match<'t>> {
+        self.groups.get( i ).copied().flatten().map( |(start, end)| Match{ text: self.text, start, end })
+    }
+
+    /// The number of groups, including group 0.
+    pub fn len( &self ) -> usize { self.groups.len() }
+
+    /// Always false, since group 0 is always present.
+    pub fn is_empty( &self ) -> bool { self.groups.is_empty() }
+
+    /// Iterates over all groups, including group 0.
+    pub fn iter<'c>( &'c self ) -> impl Iterator<Item=Option<Match<'t>>> + 'c {
+        (0..self.groups.len()).map( move |i| self.get(i) )
+    }
+
+    /// Expands `&`, `\0` and `\1` to `\9` in `replacement` with the corresponding groups, as
+    /// `regsub` does. `\&` and `\\` stand for literal `&` and `\`.
+    pub fn expand( &self, replacement: &str, dst: &mut String ) {
+        let group = |i: usize| self.get(i).map( |m| m.as_str() ).unwrap_or_default();
+
+        let mut chars = replacement.chars().peekable();
+        while let Some( c ) = chars.next() {
+            match c {
+                '&' => dst.push_str( group(0) ),
+                '\\' => match chars.peek().copied() {
+                    Some( d @ '0'..='9' ) => {
+                        chars.next();
+                        dst.push_str( group( d as usize - '0' as usize ));
+                    },
+                    Some( e @ ( '&' | '\\' )) => {
+                        chars.next();
+                        dst.push( e );
+                    },
+                    _ => dst.push( '\\' ),
+                },
+                _ => dst.push( c ),
+            }
+        }
+    }
+}
+
+// Whether Tcl counts characters beyond the BMP as surrogate pairs, i.e. `TCL_UTF_MAX` is 3.
+fn tcl_uses_surrogates() -> bool {
+    static SURROGATES: OnceLock<bool> = OnceLock::new();
+    *SURROGATES.get_or_init( || {
+        let obj = Obj::from( "\u{10000}" );
+        unsafe{ clib::Tcl_GetCharLength( obj.as_ptr() ) == 2 }
+    })
+}
+
+// Byte offsets of Tcl's character indices in `text`, with one extra for the end.
+fn byte_offsets( text: &str ) -> Vec<usize> {
+    let surrogates = tcl_uses_surrogates();
+    let mut offsets = Vec::with_capacity( text.len() + 1 );
+    for (offset, c) in text.char_indices() {
+        offsets.push( offset );
+        if surrogates && c as u32 > 0xffff {
+            offsets.push( offset );
+        }
+    }
+    offsets.push( text.len() );
+    offsets
+}
+
+// The text to search, converted once for repeated matching.
+struct Subject<'t> {
+    text    : &'t str,
+    obj     : Obj,
+    offsets : Vec<usize>,
+}
+
+impl<'t> Subject<'t> {
+    fn new( text: &'t str ) -> Self {
+        Subject{ text, obj: Obj::from( text ), offsets: byte_offsets( text )}
+    }
+
+    fn char_index( &self, byte_offset: usize ) -> usize {
+        self.offsets.partition_point( |&offset| offset < byte_offset )
+    }
+}
+
+/// A compiled Tcl regular expression.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::regexp::{Flags, Regex};
+///
+/// let re = Regex::with_flags( r"(\w+)@(\w+)\.com", Flags::NOCASE ).unwrap();
+/// let caps = re.captures( "Mail: John@Example.COM" ).unwrap();
+/// assert_eq!( caps.get(1).unwrap().as_str(), "John" );
+/// assert_eq!( caps.get(2).unwrap().range(), 11..18 );
+///
+/// let re = Regex::new( r"(\d+)-(\d+)" ).unwrap();
+/// assert_eq!( re.replace_all( "1-2, 3-4", r"\2-\1 (&)" ), "2-1 (1-2), 4-3 (3-4)" );
+///
+/// assert!( Regex::new( "a(b" ).is_err() );
+/// ```
+#[derive( Clone, Debug )]
+pub struct Regex {
+    pattern : Obj,
+    flags   : Flags,
+}
+
+impl Regex {
+    /// Compiles `pattern` as an advanced regular expression.
+    pub fn new( pattern: &str ) -> Result<Self, InvalidRegex> {
+        Regex::with_flags( pattern, Flags::NONE )
+    }
+
+    /// Compiles `pattern` as an advanced regular expression with `flags`.
+    pub fn with_flags( pattern: &str, flags: Flags ) -> Result<Self, InvalidRegex> {
+        let regex = Regex{ pattern: Obj::from( pattern ), flags };
+        interp::with_scratch( |interp| {
+            if regex.compile( interp.as_ptr() ).is_null() {
+                Err( InvalidRegex( interp.error() ))
+            } else {
+                Ok( regex )
+            }
+        })
+    }
+
+    /// The source pattern.
+    pub fn pattern( &self ) -> String { self.pattern.get_string() }
+
+    /// The compilation flags.
+    pub fn flags( &self ) -> Flags { self.flags }
+
+    // The compiled regex is cached in the internal representation of `pattern`.
+    fn compile( &self, interp: *mut clib::Tcl_Interp ) -> clib::Tcl_RegExp {
+        unsafe {
+            clib::Tcl_GetRegExpFromObj( interp, self.pattern.as_ptr(), clib::TCL_REG_ADVANCED as c_int | self.flags.0 )
+        }
+    }
+
+    // Matches at or after the character index `start`, returning the byte offsets of groups.
+    fn exec( &self, subject: &Subject, start: usize ) -> Option<Vec<Option<(usize, usize)>>> {
+        let regexp = self.compile( ptr::null_mut() );
+        if regexp.is_null() {
+            return None;
+        }
+
+        let eflags = if start == 0 { 0 } else { clib::TCL_REG_NOTBOL as c_int };
+        let found = unsafe {
+            clib::Tcl_RegExpExecObj( ptr::null_mut(), regexp, subject.obj.as_ptr(), start as c_int, -1, eflags )
+        };
+        if found != 1 {
+            return None;
+        }
+
+        let mut info = unsafe{ std::mem::zeroed::<clib::Tcl_RegExpInfo>() };
+        unsafe{ clib::Tcl_RegExpGetInfo( regexp, &mut info ); }
+
+        let last = subject.offsets.len() - 1;
+        let groups = ( 0..=info.nsubs as usize ).map( |i| {
+            let indices = unsafe{ *info.matches.add(i) };
+            if indices.start < 0 || indices.end < 0 {
+                None
+            } else {
+                let byte_offset = |index: usize| subject.offsets[ index.min( last )];
+                Some(( byte_offset( start + indices.start as usize ), byte_offset( start + indices.end as usize )))
+            }
+        }).collect();
+
+        Some( groups )
+    }
+
+    /// Checks if the regex matches somewhere in `text`.
+    pub fn is_match( &self, text: &str ) -> bool {
+        self.exec( &Subject::new( text ), 0 ).is_some()
+    }
+
+    /// Returns the leftmost match in `text`.
+    pub fn find<'t>( &self, text: &'t str ) -> Option<Match<'t>> {
+        self.captures( text ).and_then( |caps| caps.get(0) )
+    }
+
+    /// Returns the groups of the leftmost match in `text`.
+    pub fn captures<'t>( &self, text: &'t str ) -> Option<Captures<'t>> {
+        self.exec( &Subject::new( text ), 0 ).map( |groups| Captures{ text, groups })
+    }
+
+    /// Iterates over the groups of successive non-overlapping matches in `text`, as `regexp -all`.
+    pub fn captures_iter<'r,'t>( &'r self, text: &'t str ) -> CapturesIter<'r,'t> {
+        CapturesIter{ regex: self, subject: Subject::new( text ), next: Some( 0 )}
+    }
+
+    /// Iterates over successive non-overlapping matches in `text`, as `regexp -all`.
+    pub fn find_iter<'r,'t>( &'r self, text: &'t str ) -> impl Iterator<Item=Match<'t>> + 'r
+        where 't: 'r
+    {
+        self.captures_iter( text ).filter_map( |caps| caps.get(0) )
+    }
+
+    /// Replaces the leftmost match in `text` with `replacement`, as `regsub`.
+    /// See `Captures::expand()` for the substitutions in `replacement`.
+    pub fn replace( &self, text: &str, replacement: &str ) -> String {
+        self.replacen( text, 1, replacement )
+    }
+
+    /// Replaces all non-overlapping matches in `text` with `replacement`, as `regsub -all`.
+    /// See `Captures::expand()` for the substitutions in `replacement`.
+    pub fn replace_all( &self, text: &str, replacement: &str ) -> String {
+        self.replacen( text, usize::MAX, replacement )
+    }
+
+    // Unlike `regexp -all`, `regsub -all` also tries matching at the end of the text, and an
+    // empty match at the current position consumes one character, which is kept.
+    fn replacen( &self, text: &str, limit: usize, replacement: &str ) -> String {
+        let subject = Subject::new( text );
+        let len = subject.offsets.len() - 1;
+        let mut result = String::with_capacity( text.len() );
+        let mut offset = 0;
+        let mut count = 0;
+
+        while offset <= len && count < limit {
+            let groups = match self.exec( &subject, offset ) {
+                Some( groups ) => groups,
+                None => break,
+            };
+            count += 1;
+
+            let (match_start, match_end) = groups[0].expect( "group 0 should always match" );
+            result.push_str( &text[ subject.offsets[ offset ]..match_start ]);
+            Captures{ text, groups }.expand( replacement, &mut result );
+
+            let end = subject.char_index( match_end );
+            if end == offset {
+                if offset < len {
+                    result.push_str( &text[ subject.offsets[ offset ]..subject.offsets[ offset+1 ]]);
+                }
+                offset += 1;
+            } else {
+                offset = end;
+            }
+        }
+
+        if offset < len {
+            result.push_str( &text[ subject.offsets[ offset ].. ]);
+        }
+        result
+    }
+}
+
+/// Iterator over the groups of successive matches, created by `Regex::captures_iter()`.
+pub struct CapturesIter<'r,'t> {
+    regex   : &'r Regex,
+    subject : Subject<'t>,
+    next    : Option<usize>,
+}
+
+impl<'r,'t> Iterator for CapturesIter<'r,'t> {
+    type Item = Captures<'t>;
+
+    fn next( &mut self ) -> Option<Captures<'t>> {
+        let start = self.next.take()?;
+        let groups = self.regex.exec( &self.subject, start )?;
+        let (match_start, match_end) = groups[0].expect( "group 0 should always match" );
+
+        // Like `regexp -all`, an empty match advances by one character,
+        // and no more matching is tried at the end of the text.
+        let mut next = self.subject.char_index( match_end );
+        if match_start == match_end {
+            next += 1;
+        }
+        if next < self.subject.offsets.len() - 1 {
+            self.next = Some( next );
+        }
+
+        Some( Captures{ text: self.subject.text, groups })
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iteration_and_flags() {
+        let re = Regex::with_flags( "^ab", Flags::LINE | Flags::NOCASE ).unwrap();
+        let matches = re.find_iter( "AB\nxab\nab" ).map( |m| m.range() ).collect::<Vec<_>>();
+        assert_eq!( matches, vec![ 0..2, 7..9 ]);
+
+        let re = Regex::new( "x*" ).unwrap();
+        assert_eq!( re.replace_all( "abc", "-" ), "-a-b-c-" );
+        assert_eq!( re.replace_all( "äxb", "-" ), "-ä--b-" );
+        assert_eq!( re.replace_all( "", "-" ), "-" );
+        assert_eq!( re.replace( "abc", "-" ), "-abc" );
+        assert_eq!( re.find_iter( "abc" ).count(), 3 );
+
+        let re = Regex::new( "b*" ).unwrap();
+        assert_eq!( re.replace_all( "abc", "-" ), "-a--c-" );
+
+        let re = Regex::new( r"(a)|(b)" ).unwrap();
+        let caps = re.captures( "b" ).unwrap();
+        assert_eq!( caps.len(), 3 );
+        assert!( caps.get(1).is_none() );
+        assert_eq!( re.replace_all( "ab", r"[\1\2\&\\]" ), r"[a&\][b&\]" );
+    }
+}
@@ -0,0 +1,225 @@
+//! Read-eval-print loop, usable both as an interactive console and as an embedded one.
+//!
+//! Lines are accumulated until `Tcl_CommandComplete()` reports a complete command, which
+//! is then recorded in Tcl's `history` and evaluated at global level, as `tclsh` does.
+
+use crate::{
+    Obj,
+    interp::Interp,
+};
+
+use std::{
+    ffi::CString,
+    fmt::{self, Display},
+    io::{self, BufRead, Write},
+    os::raw::c_int,
+};
+
+/// The outcome of feeding one line to a `Repl`.
+#[derive( Clone, Debug, PartialEq, Eq )]
+pub enum Reply {
+    /// The accumulated input is not a complete command yet.
+    Incomplete,
+    /// The command succeeded, with its result.
+    Ok( String ),
+    /// The command failed, with its error message and the value of `errorInfo`.
+    Err{ message: String, info: String },
+}
+
+impl Display for Reply {
+    /// Formats the reply the way `tclsh` prints it: nothing for incomplete input or empty
+    /// results, otherwise the result or the error info, followed by a newline.
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        match self {
+            Reply::Incomplete                        => Ok(()),
+            Reply::Ok( result ) if result.is_empty() => Ok(()),
+            Reply::Ok( result )                      => writeln!( f, "{}", result ),
+            Reply::Err{ info, .. }                   => writeln!( f, "{}", info ),
+        }
+    }
+}
+
+/// A read-eval-print loop on an interpreter.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::*;
+/// use tcl::repl::{Repl, Reply};
+///
+/// let interpreter = Interpreter::new().unwrap();
+/// let mut repl = Repl::new( &interpreter );
+///
+/// assert_eq!( repl.feed( "proc double {x} {" ), Reply::Incomplete );
+/// assert_eq!( repl.prompt(), "> " );
+/// assert_eq!( repl.feed( "    expr {$x * 2}" ), Reply::Incomplete );
+/// assert_eq!( repl.feed( "}" ), Reply::Ok( String::new() ));
+///
+/// assert_eq!( repl.eval_input( "double 21\nset undefined" ),
+///     "42\ncan't read \"undefined\": no such variable\n    while executing\n\"set undefined\"\n" );
+/// assert_eq!( repl.history().len(), 3 );
+/// ```
+pub struct Repl {
+    interp        : Interp,
+    pending       : String,
+    history       : Vec<String>,
+    history_limit : usize,
+    prompt1       : String,
+    prompt2       : String,
+}
+
+impl Repl {
+    /// Creates a REPL evaluating commands in `interp`, with `tclsh`'s prompts `"% "` and `"> "`.
+    pub fn new( interp: &Interp ) -> Self {
+        Repl {
+            interp        : interp.clone(),
+            pending       : String::new(),
+            history       : Vec::new(),
+            history_limit : 100,
+            prompt1       : "% ".to_owned(),
+            prompt2       : "> ".to_owned(),
+        }
+    }
+
+    /// Sets the prompt for a new command and the prompt for continuation lines.
+    pub fn set_prompts( &mut self, prompt1: &str, prompt2: &str ) {
+        self.prompt1 = prompt1.to_owned();
+        self.prompt2 = prompt2.to_owned();
+    }
+
+    /// The prompt to show before reading the next line.
+    pub fn prompt( &self ) -> &str {
+        if self.pending.is_empty() { &self.prompt1 } else { &self.prompt2 }
+    }
+
+    /// The lines fed so far which have not formed a complete command yet.
+    pub fn pending( &self ) -> &str { &self.pending }
+
+    /// Discards the incomplete command, if any.
+    pub fn discard_pending( &mut self ) { self.pending.clear(); }
+
+    /// The commands evaluated so far, oldest first.
+    pub fn history( &self ) -> &[String] { &self.history }
+
+    /// Sets how many commands `history()` keeps, 100 by default.
+    /// Tcl's own `history` command has its own limit, see `history keep`.
+    pub fn set_history_limit( &mut self, limit: usize ) {
+        self.history_limit = limit;
+        self.trim_history();
+    }
+
+    fn trim_history( &mut self ) {
+        if self.history.len() > self.history_limit {
+            let excess = self.history.len() - self.history_limit;
+            self.history.drain( ..excess );
+        }
+    }
+
+    /// Feeds one line of input, evaluating the accumulated input once it is a complete command.
+    pub fn feed( &mut self, line: &str ) -> Reply {
+        self.pending.push_str( line.trim_end_matches( ['\r', '\n'] ));
+        self.pending.push( '\n' );
+
+        let complete = CString::new( self.pending.as_str() )
+            .map( |script| unsafe{ clib::Tcl_CommandComplete( script.as_ptr() )} != 0 )
+            .unwrap_or( true );
+        if !complete {
+            return Reply::Incomplete;
+        }
+
+        let script = std::mem::take( &mut self.pending );
+        let command = script.trim_end_matches( '\n' );
+
+        if command.trim().is_empty() {
+            return Reply::Ok( String::new() );
+        }
+
+        self.history.push( command.to_owned() );
+        self.trim_history();
+
+        let command = Obj::from( command );
+        let code = unsafe {
+            clib::Tcl_RecordAndEvalObj( self.interp.as_ptr(), command.as_ptr(), clib::TCL_EVAL_GLOBAL as c_int )
+        };
+
+        if code == clib::TCL_ERROR as c_int {
+            let error = self.interp.error();
+            Reply::Err{ message: error.obj.get_string(), info: error.info() }
+        } else {
+            Reply::Ok( self.interp.result().get_string() )
+        }
+    }
+
+    /// Feeds `input` line by line, returning what a terminal session would have printed,
+    /// excluding prompts.
+    pub fn eval_input( &mut self, input: &str ) -> String {
+        input.lines().map( |line| self.feed( line ).to_string() ).collect()
+    }
+
+    /// Runs the loop on `input` and `output` until the end of input. Results are written to
+    /// `output` as well as errors, preceded by prompts.
+    pub fn run_with( &mut self, mut input: impl BufRead, mut output: impl Write ) -> io::Result<()> {
+        let mut line = String::new();
+        loop {
+            write!( output, "{}", self.prompt() )?;
+            output.flush()?;
+
+            line.clear();
+            if input.read_line( &mut line )? == 0 {
+                return Ok(());
+            }
+
+            write!( output, "{}", self.feed( &line ))?;
+        }
+    }
+
+    /// Runs the loop on the terminal until the end of standard input. Results are written
+    /// to standard output, and errors to standard error.
+    pub fn run( &mut self ) -> io::Result<()> {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        let mut line = String::new();
+        loop {
+            print!( "{}", self.prompt() );
+            io::stdout().flush()?;
+
+            line.clear();
+            if input.read_line( &mut line )? == 0 {
+                println!();
+                return Ok(());
+            }
+
+            match self.feed( &line ) {
+                reply @ Reply::Err{..} => eprint!( "{}", reply ),
+                reply                  => print!( "{}", reply ),
+            }
+        }
+    }
+}
+
+impl Interp {
+    /// Runs an interactive read-eval-print loop on the terminal until the end of standard input.
+    pub fn repl( &self ) -> io::Result<()> {
+        Repl::new( self ).run()
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn run_with_prompts_and_history() {
+        let interpreter = Interpreter::new().unwrap();
+        let mut repl = Repl::new( &interpreter );
+        repl.set_history_limit( 2 );
+
+        let mut output = Vec::new();
+        repl.run_with( "set a {\n1}\nincr b\nincr b\n".as_bytes(), &mut output ).unwrap();
+
+        assert_eq!( String::from_utf8( output ).unwrap(), "% > \n1\n% 1\n% 2\n% " );
+        assert_eq!( repl.history(), ["incr b", "incr b"] );
+        assert!( repl.eval_input( "history info" ).contains( "incr b" ));
+    }
+}
@@ -0,0 +1,256 @@
+//! Snapshots of an interpreter's global state, for saving sessions or recovering from crashes.
+//!
+//! A snapshot holds the values of scalar and array variables in all namespaces, and
+//! optionally the definitions of procs. It is serde-serializable, and can be restored into
+//! another interpreter.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::*;
+//! use tcl::snapshot::SnapshotFilter;
+//!
+//! let interp = Interpreter::new().unwrap();
+//! interp.run( "set user alice; array set prefs {theme dark}; namespace eval app { variable count 3 }" ).unwrap();
+//! interp.run( "proc greet {{who world}} { return \"hello $who\" }" ).unwrap();
+//!
+//! let snapshot = interp.snapshot( &SnapshotFilter::new().procs( true )).unwrap();
+//! assert_eq!( snapshot.scalars["::app::count"], "3" );
+//!
+//! let fresh = Interpreter::new().unwrap();
+//! fresh.restore( &snapshot ).unwrap();
+//! assert_eq!( fresh.eval( "list $user $prefs(theme) $app::count [greet]" ).unwrap().to_string(),
+//!     "alice dark 3 {hello world}" );
+//! ```
+
+use crate::{
+    Interp,
+    Obj,
+    error::InterpError,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{
+    collections::BTreeMap,
+    ffi::CString,
+};
+
+type Result<T, E=InterpError> = std::result::Result<T,E>;
+
+// Variables and procs of Tcl itself and of common packages, excluded by default.
+const BUILTINS: &[&str] = &[
+    "::env", "::argc", "::argv", "::argv0", "::errorCode", "::errorInfo",
+    "::auto_*", "::tcl_*", "::tclLog", "::tclPkgSetup", "::tclPkgUnknown", "::tk_*",
+    "::unknown", "::history", "::pkg_mkIndex", "::parray",
+    "::tcl::*", "::oo::*", "::msgcat::*", "::tk::*", "::ttk::*",
+];
+
+/// Selects what a snapshot captures, by glob patterns of fully qualified names such as
+/// `::app::*`, as in `string match`.
+#[derive( Clone, Debug )]
+pub struct SnapshotFilter {
+    include : Vec<String>,
+    exclude : Vec<String>,
+    procs   : bool,
+}
+
+impl Default for SnapshotFilter {
+    fn default() -> Self { SnapshotFilter::new() }
+}
+
+impl SnapshotFilter {
+    /// Captures all variables except those of Tcl, Tk and their bundled packages, and no procs.
+    pub fn new() -> Self {
+        SnapshotFilter {
+            include : Vec::new(),
+            exclude : BUILTINS.iter().map( |&pattern| pattern.to_owned() ).collect(),
+            procs   : false,
+        }
+    }
+
+    /// Captures only names matching `pattern`, or any of the other included patterns.
+    pub fn include( mut self, pattern: &str ) -> Self {
+        self.include.push( pattern.to_owned() );
+        self
+    }
+
+    /// Does not capture names matching `pattern`.
+    pub fn exclude( mut self, pattern: &str ) -> Self {
+        self.exclude.push( pattern.to_owned() );
+        self
+    }
+
+    /// Captures the definitions of procs too, if `procs` is true.
+    pub fn procs( mut self, procs: bool ) -> Self {
+        self.procs = procs;
+        self
+    }
+
+    /// Checks if the fully qualified `name` is to be captured.
+    pub fn matches( &self, name: &str ) -> bool {
+        let string_match = |pattern: &String| {
+            let name = CString::new( name ).unwrap_or_default();
+            let pattern = CString::new( pattern.as_str() ).unwrap_or_default();
+            unsafe{ clib::Tcl_StringMatch( name.as_ptr(), pattern.as_ptr() ) != 0 }
+        };
+        ( self.include.is_empty() || self.include.iter().any( string_match ))
+            && !self.exclude.iter().any( string_match )
+    }
+}
+
+/// The definition of a proc.
+#[derive( Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize )]
+pub struct ProcDef {
+    /// The arguments, with their default values if any.
+    pub args : Vec<(String, Option<String>)>,
+    /// The body script.
+    pub body : String,
+}
+
+/// Captured global state of an interpreter, keyed by fully qualified names.
+#[derive( Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize )]
+pub struct InterpSnapshot {
+    /// Values of scalar variables.
+    pub scalars : BTreeMap<String, String>,
+    /// Elements of array variables.
+    pub arrays  : BTreeMap<String, BTreeMap<String, String>>,
+    /// Definitions of procs.
+    pub procs   : BTreeMap<String, ProcDef>,
+}
+
+// Lists the fully qualified names of `info vars` or `info procs` in the namespace.
+fn names_in( interp: &Interp, info: &str, namespace: &str ) -> Result<Vec<String>> {
+    let prefix = if namespace == "::" { "::".to_owned() } else { format!( "{}::", namespace )};
+    let names = interp.eval(( "info", info, format!( "{}*", prefix )))?;
+    Ok( elements( names ).map( |name| {
+        let name = name.get_string();
+        format!( "{}{}", prefix, name.rsplit( "::" ).next().unwrap_or_default() )
+    }).collect() )
+}
+
+fn elements( list: Obj ) -> impl Iterator<Item=Obj> {
+    list.get_elements().ok().into_iter().flatten()
+}
+
+// Creates the namespace enclosing the fully qualified `name`, if missing.
+fn create_namespace_of( interp: &Interp, name: &str ) -> Result<()> {
+    match name.rfind( "::" ) {
+        Some( end ) if end > 0 => interp.run(( "namespace", "eval", &name[ ..end ], "" )),
+        _ => Ok(()),
+    }
+}
+
+impl Interp {
+    /// Captures the variables, and optionally the procs, selected by `filter`.
+    pub fn snapshot( &self, filter: &SnapshotFilter ) -> Result<InterpSnapshot> {
+        let mut snapshot = InterpSnapshot::default();
+        let mut namespaces = vec![ "::".to_owned() ];
+
+        while let Some( namespace ) = namespaces.pop() {
+            namespaces.extend( elements( self.eval(( "namespace", "children", namespace.as_str() ))? )
+                .map( |child| child.get_string() ));
+
+            for name in names_in( self, "vars", &namespace )? {
+                if !filter.matches( &name ) {
+                    continue;
+                }
+                if self.eval(( "array", "exists", name.as_str() ))?.as_bool() {
+                    let mut elems = BTreeMap::new();
+                    let mut pairs = elements( self.eval(( "array", "get", name.as_str() ))? );
+                    while let ( Some( key ), Some( value )) = ( pairs.next(), pairs.next() ) {
+                        elems.insert( key.get_string(), value.get_string() );
+                    }
+                    snapshot.arrays.insert( name, elems );
+                } else if self.eval(( "info", "exists", name.as_str() ))?.as_bool() {
+                    let value = self.get( name.as_str() )?.get_string();
+                    snapshot.scalars.insert( name, value );
+                }
+            }
+
+            if filter.procs {
+                for name in names_in( self, "procs", &namespace )? {
+                    if !filter.matches( &name ) {
+                        continue;
+                    }
+                    let args = elements( self.eval(( "apply", "{proc} {
+                        lmap arg [info args $proc] {
+                            if {[info default $proc $arg value]} { list $arg $value } else { list $arg }
+                        }
+                    }", name.as_str() ))? ).map( |arg| {
+                        let mut spec = elements( arg ).map( |word| word.get_string() );
+                        ( spec.next().unwrap_or_default(), spec.next() )
+                    }).collect();
+                    let body = self.eval(( "info", "body", name.as_str() ))?.get_string();
+                    snapshot.procs.insert( name, ProcDef{ args, body });
+                }
+            }
+        }
+
+        Ok( snapshot )
+    }
+
+    /// Reapplies `snapshot`, creating missing namespaces and replacing existing variables and
+    /// procs of the same names.
+    pub fn restore( &self, snapshot: &InterpSnapshot ) -> Result<()> {
+        for ( name, value ) in &snapshot.scalars {
+            create_namespace_of( self, name )?;
+            self.run(( "unset", "-nocomplain", name.as_str() ))?;
+            self.run(( "set", name.as_str(), value.as_str() ))?;
+        }
+
+        for ( name, elems ) in &snapshot.arrays {
+            create_namespace_of( self, name )?;
+            self.run(( "unset", "-nocomplain", name.as_str() ))?;
+            let pairs = Obj::new_list( elems.iter().flat_map( |( key, value )| [ Obj::from( key.as_str() ), Obj::from( value.as_str() )]));
+            self.run(( "array", "set", name.as_str(), pairs ))?;
+        }
+
+        for ( name, proc_def ) in &snapshot.procs {
+            create_namespace_of( self, name )?;
+            let args = Obj::new_list( proc_def.args.iter().map( |( arg, default )| match default {
+                Some( default ) => Obj::from(( arg.as_str(), default.as_str() )),
+                None => Obj::from(( arg.as_str(), )),
+            }));
+            self.run(( "proc", name.as_str(), args, proc_def.body.as_str() ))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::*;
+    use super::{InterpSnapshot, SnapshotFilter};
+
+    #[test]
+    fn json_round_trip() {
+        let interp = Interpreter::new().unwrap();
+        interp.run( r#"
+            set greeting "hello world"
+            array set sizes {small 1 large {10 20}}
+            namespace eval ::app::ui { variable title {Main Window}; variable declared_only }
+            proc ::app::add {a {b 1}} { expr {$a + $b} }
+            set skipped 0
+        "# ).unwrap();
+
+        let filter = SnapshotFilter::new().exclude( "::skipped" ).procs( true );
+        let snapshot = interp.snapshot( &filter ).unwrap();
+        assert!( !snapshot.scalars.contains_key( "::skipped" ));
+        assert!( !snapshot.scalars.contains_key( "::app::ui::declared_only" ));
+        assert!( !snapshot.scalars.contains_key( "::tcl_version" ));
+
+        let json = serde_json::to_string( &snapshot ).unwrap();
+        let restored: InterpSnapshot = serde_json::from_str( &json ).unwrap();
+        assert_eq!( restored, snapshot );
+
+        let fresh = Interpreter::new().unwrap();
+        fresh.restore( &restored ).unwrap();
+        assert_eq!( fresh.eval( "set greeting" ).unwrap().to_string(), "hello world" );
+        assert_eq!( fresh.eval( "set sizes(large)" ).unwrap().to_string(), "10 20" );
+        assert_eq!( fresh.eval( "set ::app::ui::title" ).unwrap().to_string(), "Main Window" );
+        assert_eq!( fresh.eval( "::app::add 2" ).unwrap().as_i32(), 3 );
+        assert_eq!( fresh.snapshot( &filter ).unwrap(), snapshot );
+    }
+}
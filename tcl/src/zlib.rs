@@ -0,0 +1,379 @@
+//! Compression, decompression and checksums backed by Tcl's built-in zlib.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::zlib;
+//!
+//! let data = b"hello, hello, hello, hello".to_vec();
+//! let compressed = zlib::gzip( &data, zlib::DEFAULT_LEVEL )?;
+//! assert_eq!( zlib::gunzip( &compressed )?, data );
+//! assert_eq!( zlib::crc32( b"hello" ), 0x3610a686 );
+//!
+//! # Ok::<(),tcl::TclError>(())
+//! ```
+
+use crate::{
+    Obj,
+    error::InterpError,
+    interp,
+};
+
+use std::{
+    io::{self, Read, Write},
+    os::raw::{c_int, c_uint},
+    ptr,
+    slice,
+};
+
+type Result<T, E=InterpError> = std::result::Result<T,E>;
+
+/// Compression level providing the default trade-off between speed and size.
+pub const DEFAULT_LEVEL: c_int = clib::TCL_ZLIB_COMPRESS_DEFAULT;
+/// Compression level storing data without compression.
+pub const NO_COMPRESSION: c_int = clib::TCL_ZLIB_COMPRESS_NONE as c_int;
+/// Compression level favoring speed.
+pub const FAST: c_int = clib::TCL_ZLIB_COMPRESS_FAST as c_int;
+/// Compression level favoring size.
+pub const BEST: c_int = clib::TCL_ZLIB_COMPRESS_BEST as c_int;
+
+/// Formats of compressed data.
+#[derive( Clone, Copy, Debug, PartialEq, Eq )]
+pub enum Format {
+    /// Raw deflate stream, without header nor checksum.
+    Raw,
+    /// Zlib format, with a small header and an Adler-32 checksum.
+    Zlib,
+    /// Gzip format, with a header and a CRC-32 checksum.
+    Gzip,
+    /// Detects zlib or gzip format when decompressing.
+    Auto,
+}
+
+impl Format {
+    fn to_c_int( self ) -> c_int {
+        ( match self {
+            Format::Raw  => clib::TCL_ZLIB_FORMAT_RAW,
+            Format::Zlib => clib::TCL_ZLIB_FORMAT_ZLIB,
+            Format::Gzip => clib::TCL_ZLIB_FORMAT_GZIP,
+            Format::Auto => clib::TCL_ZLIB_FORMAT_AUTO,
+        }) as c_int
+    }
+}
+
+pub(crate) fn byte_array( bytes: &[u8] ) -> Obj {
+    crate::init();
+    unsafe{ Obj::from_raw( clib::Tcl_NewByteArrayObj( bytes.as_ptr(), bytes.len() as c_int ))}
+}
+
+pub(crate) fn bytes_of( obj: &Obj ) -> Vec<u8> {
+    let mut len: c_int = 0;
+    unsafe {
+        let data = clib::Tcl_GetByteArrayFromObj( obj.as_ptr(), &mut len );
+        if data.is_null() || len == 0 {
+            Vec::new()
+        } else {
+            slice::from_raw_parts( data, len as usize ).to_vec()
+        }
+    }
+}
+
+/// Compresses `data` in `format` at `level`, which is 0 to 9, or `DEFAULT_LEVEL`.
+pub fn compress_with( format: Format, data: &[u8], level: c_int ) -> Result<Vec<u8>> {
+    interp::with_scratch( |interp| {
+        let data = byte_array( data );
+        let code = unsafe{ clib::Tcl_ZlibDeflate( interp.as_ptr(), format.to_c_int(), data.as_ptr(), level, ptr::null_mut() )};
+        if code == clib::TCL_OK as c_int {
+            Ok( bytes_of( &interp.result() ))
+        } else {
+            Err( interp.error() )
+        }
+    })
+}
+
+/// Decompresses `data` in `format`.
+pub fn decompress_with( format: Format, data: &[u8] ) -> Result<Vec<u8>> {
+    interp::with_scratch( |interp| {
+        let data = byte_array( data );
+        let code = unsafe{ clib::Tcl_ZlibInflate( interp.as_ptr(), format.to_c_int(), data.as_ptr(), 0, ptr::null_mut() )};
+        if code == clib::TCL_OK as c_int {
+            Ok( bytes_of( &interp.result() ))
+        } else {
+            Err( interp.error() )
+        }
+    })
+}
+
+/// Compresses `data` in raw deflate format.
+pub fn deflate( data: &[u8], level: c_int ) -> Result<Vec<u8>> { compress_with( Format::Raw, data, level )}
+
+/// Decompresses `data` in raw deflate format.
+pub fn inflate( data: &[u8] ) -> Result<Vec<u8>> { decompress_with( Format::Raw, data )}
+
+/// Compresses `data` in zlib format.
+pub fn compress( data: &[u8], level: c_int ) -> Result<Vec<u8>> { compress_with( Format::Zlib, data, level )}
+
+/// Decompresses `data` in zlib format.
+pub fn decompress( data: &[u8] ) -> Result<Vec<u8>> { decompress_with( Format::Zlib, data )}
+
+/// Compresses `data` in gzip format.
+pub fn gzip( data: &[u8], level: c_int ) -> Result<Vec<u8>> { compress_with( Format::Gzip, data, level )}
+
+/// Decompresses `data` in gzip format.
+pub fn gunzip( data: &[u8] ) -> Result<Vec<u8>> { decompress_with( Format::Gzip, data )}
+
+/// Computes the CRC-32 checksum of `data`.
+pub fn crc32( data: &[u8] ) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update( data );
+    crc.value()
+}
+
+/// Computes the Adler-32 checksum of `data`.
+pub fn adler32( data: &[u8] ) -> u32 {
+    let mut adler = Adler32::new();
+    adler.update( data );
+    adler.value()
+}
+
+/// CRC-32 checksum computed over data fed in pieces.
+#[derive( Clone, Copy, Debug )]
+pub struct Crc32( u32 );
+
+impl Crc32 {
+    /// Creates the checksum of empty data.
+    pub fn new() -> Self {
+        crate::init();
+        Crc32( unsafe{ clib::Tcl_ZlibCRC32( 0, ptr::null(), 0 )})
+    }
+
+    /// Updates the checksum with `data`.
+    pub fn update( &mut self, data: &[u8] ) {
+        self.0 = unsafe{ clib::Tcl_ZlibCRC32( self.0, data.as_ptr(), data.len() as c_int )};
+    }
+
+    /// The checksum of the data fed so far.
+    pub fn value( &self ) -> u32 { self.0 }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self { Crc32::new() }
+}
+
+/// Adler-32 checksum computed over data fed in pieces.
+#[derive( Clone, Copy, Debug )]
+pub struct Adler32( u32 );
+
+impl Adler32 {
+    /// Creates the checksum of empty data.
+    pub fn new() -> Self {
+        crate::init();
+        Adler32( unsafe{ clib::Tcl_ZlibAdler32( 0, ptr::null(), 0 )})
+    }
+
+    /// Updates the checksum with `data`.
+    pub fn update( &mut self, data: &[u8] ) {
+        self.0 = unsafe{ clib::Tcl_ZlibAdler32( self.0, data.as_ptr(), data.len() as c_int )};
+    }
+
+    /// The checksum of the data fed so far.
+    pub fn value( &self ) -> u32 { self.0 }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self { Adler32::new() }
+}
+
+/// A compressing or decompressing stream. Input is written to it, and output is read from it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::{Read, Write};
+/// use tcl::zlib::{self, Format, ZlibStream};
+///
+/// let mut deflater = ZlibStream::deflate( Format::Gzip, zlib::BEST )?;
+/// deflater.write_all( b"project data, " )?;
+/// deflater.write_all( b"project data" )?;
+/// deflater.finish()?;
+///
+/// let mut compressed = Vec::new();
+/// deflater.read_to_end( &mut compressed )?;
+///
+/// let mut inflater = ZlibStream::inflate( Format::Auto )?;
+/// inflater.write_all( &compressed )?;
+/// inflater.finish()?;
+///
+/// let mut text = String::new();
+/// inflater.read_to_string( &mut text )?;
+/// assert_eq!( text, "project data, project data" );
+/// assert!( inflater.is_eof() );
+///
+/// # Ok::<(),std::io::Error>(())
+/// ```
+pub struct ZlibStream {
+    handle   : clib::Tcl_ZlibStream,
+    finished : bool,
+    unflushed: bool,
+}
+
+impl ZlibStream {
+    fn new( mode: c_uint, format: Format, level: c_int ) -> io::Result<Self> {
+        crate::init();
+        let mut handle: clib::Tcl_ZlibStream = ptr::null_mut();
+        let code = unsafe {
+            clib::Tcl_ZlibStreamInit( ptr::null_mut(), mode as c_int, format.to_c_int(), level, ptr::null_mut(), &mut handle )
+        };
+        if code == clib::TCL_OK as c_int && !handle.is_null() {
+            Ok( ZlibStream{ handle, finished: false, unflushed: false })
+        } else {
+            Err( io::Error::new( io::ErrorKind::InvalidInput, "failed to create zlib stream" ))
+        }
+    }
+
+    /// Creates a compressing stream producing `format`, at `level` which is 0 to 9, or
+    /// `DEFAULT_LEVEL`.
+    pub fn deflate( format: Format, level: c_int ) -> io::Result<Self> {
+        ZlibStream::new( clib::TCL_ZLIB_STREAM_DEFLATE, format, level )
+    }
+
+    /// Creates a decompressing stream consuming `format`.
+    pub fn inflate( format: Format ) -> io::Result<Self> {
+        ZlibStream::new( clib::TCL_ZLIB_STREAM_INFLATE, format, DEFAULT_LEVEL )
+    }
+
+    fn put( &mut self, data: &[u8], flush: c_uint ) -> io::Result<()> {
+        let data = byte_array( data );
+        let code = unsafe{ clib::Tcl_ZlibStreamPut( self.handle, data.as_ptr(), flush as c_int )};
+        if code == clib::TCL_OK as c_int {
+            Ok(())
+        } else {
+            Err( io::Error::new( io::ErrorKind::InvalidData, "zlib stream failed to process data" ))
+        }
+    }
+
+    fn get( &mut self, count: usize ) -> io::Result<Vec<u8>> {
+        let output = byte_array( &[] );
+        let code = unsafe{ clib::Tcl_ZlibStreamGet( self.handle, output.as_ptr(), count as c_int )};
+        if code == clib::TCL_OK as c_int {
+            Ok( bytes_of( &output ))
+        } else {
+            Err( io::Error::new( io::ErrorKind::InvalidData, "zlib stream failed to produce data" ))
+        }
+    }
+
+    /// Ends the input, making all remaining output available for reading.
+    pub fn finish( &mut self ) -> io::Result<()> {
+        if !self.finished {
+            self.put( &[], clib::TCL_ZLIB_FINALIZE )?;
+            self.finished = true;
+            self.unflushed = false;
+        }
+        Ok(())
+    }
+
+    /// Checks if the end of compressed data has been reached by a decompressing stream,
+    /// and all output has been read.
+    pub fn is_eof( &self ) -> bool {
+        unsafe{ clib::Tcl_ZlibStreamEof( self.handle ) != 0 }
+    }
+
+    /// The checksum of the uncompressed data processed so far: Adler-32 for zlib format,
+    /// or CRC-32 for gzip format.
+    pub fn checksum( &self ) -> u32 {
+        unsafe{ clib::Tcl_ZlibStreamChecksum( self.handle ) as u32 }
+    }
+}
+
+impl Write for ZlibStream {
+    fn write( &mut self, buf: &[u8] ) -> io::Result<usize> {
+        if self.finished {
+            return Err( io::Error::new( io::ErrorKind::BrokenPipe, "zlib stream has been finished" ));
+        }
+        self.put( buf, clib::TCL_ZLIB_NO_FLUSH )?;
+        self.unflushed = true;
+        Ok( buf.len() )
+    }
+
+    /// Makes the output of all input written so far available for reading.
+    fn flush( &mut self ) -> io::Result<()> {
+        if self.finished {
+            Ok(())
+        } else {
+            self.put( &[], clib::TCL_ZLIB_FLUSH )?;
+            self.unflushed = false;
+            Ok(())
+        }
+    }
+}
+
+impl Read for ZlibStream {
+    /// Reads the output available so far, flushing the input written if necessary. Returns 0
+    /// only at the end of output, i.e. after `finish()` or the end of compressed data. Fails
+    /// with `ErrorKind::WouldBlock` if no output is available until more input is written.
+    fn read( &mut self, buf: &mut [u8] ) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok( 0 );
+        }
+        let mut output = self.get( buf.len() )?;
+        if output.is_empty() && self.unflushed {
+            Write::flush( self )?;
+            output = self.get( buf.len() )?;
+        }
+        if output.is_empty() && !self.finished && !self.is_eof() {
+            return Err( io::Error::new( io::ErrorKind::WouldBlock, "zlib stream needs more input" ));
+        }
+        buf[ ..output.len() ].copy_from_slice( &output );
+        Ok( output.len() )
+    }
+}
+
+impl Drop for ZlibStream {
+    fn drop( &mut self ) {
+        unsafe{ clib::Tcl_ZlibStreamClose( self.handle ); }
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_checksums() {
+        let data = "Tcl/Tk ".repeat( 100 ).into_bytes();
+
+        for format in [ Format::Raw, Format::Zlib, Format::Gzip ] {
+            let compressed = compress_with( format, &data, BEST ).unwrap();
+            assert!( compressed.len() < data.len() );
+            assert_eq!( decompress_with( format, &compressed ).unwrap(), data );
+        }
+
+        assert!( decompress( b"not compressed" ).is_err() );
+
+        let mut adler = Adler32::new();
+        adler.update( &data[ ..10 ]);
+        adler.update( &data[ 10.. ]);
+        assert_eq!( adler.value(), adler32( &data ));
+
+        let mut deflater = ZlibStream::deflate( Format::Zlib, DEFAULT_LEVEL ).unwrap();
+        deflater.write_all( &data ).unwrap();
+        deflater.finish().unwrap();
+        assert_eq!( deflater.checksum(), adler32( &data ));
+
+        // reading before `finish()` neither loses data nor stops early
+        let mut deflater = ZlibStream::deflate( Format::Zlib, DEFAULT_LEVEL ).unwrap();
+        deflater.write_all( &data ).unwrap();
+        let mut compressed = vec![ 0_u8; 4096 ];
+        let mut len = deflater.read( &mut compressed ).unwrap();
+        assert!( len > 0 );
+        assert_eq!( deflater.read( &mut compressed[ len.. ]).unwrap_err().kind(), io::ErrorKind::WouldBlock );
+        deflater.finish().unwrap();
+        loop {
+            match deflater.read( &mut compressed[ len.. ]).unwrap() {
+                0 => break,
+                n => len += n,
+            }
+        }
+        assert_eq!( decompress_with( Format::Zlib, &compressed[ ..len ]).unwrap(), data );
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg( target_os = "linux" )]
+
+// `tcl::notifier::install()` must run before Tcl gets initialized,
+// so this test lives in its own process.
+
+use mio::{Events, Interest, Poll, Token, unix::SourceFd};
+
+use std::{
+    io::Write,
+    os::unix::{io::AsRawFd, net::UnixStream},
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use tcl::*;
+use tcl::notifier::{self, FileHandler, READABLE};
+
+#[test]
+fn driven_by_mio() {
+    assert!( notifier::install() );
+    assert!( notifier::is_installed() );
+
+    let interpreter = Interpreter::new().unwrap();
+    let tcl_fd = notifier::as_raw_fd().unwrap();
+
+    let mut poll = Poll::new().unwrap();
+    poll.registry().register( &mut SourceFd( &tcl_fd ), Token(0), Interest::READABLE ).unwrap();
+
+    let (reader, mut writer) = UnixStream::pair().unwrap();
+    let received = Rc::new( Cell::new( false ));
+    let received_ = received.clone();
+    let _handler = FileHandler::new( reader.as_raw_fd(), READABLE, move |_mask| received_.set( true ));
+
+    interpreter.run( "after 20 { set ::timer_fired 1 }" ).unwrap();
+    notifier::process_events();
+    assert!( notifier::timeout().is_some() );
+
+    writer.write_all( b"ping" ).unwrap();
+
+    let start = Instant::now();
+    let mut events = Events::with_capacity( 8 );
+
+    while !( received.get() && interpreter.get( "timer_fired" ).is_ok() ) {
+        assert!( start.elapsed() < Duration::from_secs(5) );
+        poll.poll( &mut events, notifier::timeout() ).unwrap();
+        notifier::process_events();
+    }
+
+    // Nothing left to do in Tcl.
+    assert!( notifier::timeout().is_none() );
+}
@@ -59,6 +59,57 @@ impl<Inst:TkInstance> Widget<Inst> {
         self.tk().run(( "bind", self.path, sequence.into(), script ))
     }
 
+    /// Binds the command created by `tclosure!()` to `sequence`, like `bind()`, with the widget
+    /// owning the command. The command is deleted, freeing the closure, when the widget is
+    /// destroyed or another command is bound to `sequence` by this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let label = tk.root().add_label( "label" -text("hover me") )?;
+    ///
+    ///     label.bind_fn( event::enter(), tclosure!( tk, move || -> TkResult<()> {
+    ///         Ok( label.configure( -relief("raised") )? )
+    ///     }))?;
+    ///     label.bind_fn( event::enter(), tclosure!( tk, move || -> TkResult<()> {
+    ///         Ok( label.configure( -relief("sunken") )? )
+    ///     }))?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn bind_fn( &self, sequence: impl Into<TkEventSeq>, script: String ) -> InterpResult<()> {
+        let sequence = Obj::from( sequence.into() );
+        self.check_alive()?;
+        self.tk().run(( "bind", self.path, sequence.clone(), script.as_str() ))?;
+        self.own_keyed_command( sequence.to_string(), &script );
+        Ok(())
+    }
+
+    // Binds the command created by `tclosure!()` to `sequence` on a bindtag private to this
+    // widget, put in front of its bindtags, for bindings of the library which neither replace
+    // nor are replaced by the ones of `bind_fn()`, and which no `break` of them skips.
+    pub(crate) fn bind_private_fn( &self, sequence: impl Into<TkEventSeq>, script: String ) -> InterpResult<()> {
+        let sequence = Obj::from( sequence.into() );
+        self.check_alive()?;
+        let tk = self.tk();
+        let tag = format!( "tk_rs_private{}", self.path );
+        if self.own_bindtag( &tag ) {
+            let tags = tk.eval(( "bindtags", self.path ))?;
+            let tags = tk.eval(( "linsert", tags, 0, tag.as_str() ))?;
+            tk.run(( "bindtags", self.path, tags ))?;
+        }
+        tk.run(( "bind", tag.as_str(), sequence.clone(), script.as_str() ))?;
+        self.own_keyed_command( format!( "{tag} {sequence}" ), &script );
+        Ok(())
+    }
+
     pub fn bindtags( &self, tags: &[&str] ) -> InterpResult<()> {
         let mut command = Vec::<Obj>::with_capacity( tags.len() + 2 );
 
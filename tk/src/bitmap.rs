@@ -1,6 +1,11 @@
 use crate::*;
 
-use tcl::Obj;
+use crate::opt::option_value;
+
+use tcl::{
+    Obj,
+    error::DeError,
+};
 
 use tuplex::*;
 
@@ -28,11 +33,13 @@ impl<Inst:TkInstance> Bitmap<Inst> {
         }
     }
 
-    pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<opt::TkBitmapOpt>
     {
-        self.tk().eval(( self.name.clone(), "cget", <Opt as TkOption>::NAME ))
+        let obj = self.tk().eval(( self.name.clone(), "cget", <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn configure<Opts>( &self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -29,6 +29,7 @@ use crate::{
         TkCanvasRectangleOpt,
         TkCanvasTextOpt,
         TkCanvasWindowOpt,
+        option_value,
     },
     range::{
         TkDefaultStart,
@@ -506,12 +507,14 @@ impl<Inst:TkInstance> TkCanvas<Inst> {
         self.tk().run(( self.path, "insert", tag_or_id.into(), before_this, to_insert ))
     }
 
-    pub fn itemcget<Opt,Val>( &self, tag_or_id: impl Into<TagOrId>, _name_fn: fn(Val)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn itemcget<Opt,Val>( &self, tag_or_id: impl Into<TagOrId>, _name_fn: fn(Val)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkCanvasItemOpt>
             , Val : Into<Obj>
     {
-        self.tk().eval(( self.path, "itemcget", tag_or_id.into(), <Opt as TkOption>::NAME ))
+        let obj = self.tk().eval(( self.path, "itemcget", tag_or_id.into(), <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn itemconfigure<Opts>( &self, tag_or_id: impl Into<TagOrId>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
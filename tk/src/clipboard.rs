@@ -0,0 +1,111 @@
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
+use crate::{
+    InterpResult,
+    Tk,
+    TkInstance,
+    error::{
+        ClipboardEmpty,
+        UnsupportedSelectionType,
+    },
+};
+
+use tcl::error::InterpError;
+
+impl<Inst:TkInstance> Tk<Inst> {
+    /// Claims ownership of the clipboard and removes its contents.
+    pub fn clipboard_clear( &self ) -> InterpResult<()> {
+        self.run(( "clipboard", "clear" ))
+    }
+
+    /// Appends `data` to the clipboard as type "STRING".
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///
+    ///     tk.clipboard_clear()?;
+    ///     tk.clipboard_append( "-hello" )?;
+    ///     tk.clipboard_append( ", world" )?;
+    ///     assert_eq!( tk.clipboard_get_text()?, "-hello, world" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn clipboard_append( &self, data: &str ) -> InterpResult<()> {
+        self.run(( "clipboard", "append", "--", data ))
+    }
+
+    /// Appends `data` to the clipboard as `type_`, which may be an atom name such as
+    /// "UTF8_STRING" or a MIME-ish name such as "text/html". The `format` is the
+    /// representation used to transmit the data, e.g. "STRING" or "ATOM".
+    pub fn clipboard_append_typed( &self, data: &str, type_: &str, format: &str ) -> InterpResult<()> {
+        self.run(( "clipboard", "append", "-type", type_, "-format", format, "--", data ))
+    }
+
+    /// Replaces the contents of the clipboard with `text`, as type "STRING".
+    pub fn clipboard_set( &self, text: &str ) -> InterpResult<()> {
+        self.clipboard_clear()?;
+        self.clipboard_append( text )
+    }
+
+    /// Replaces the contents of the clipboard with `data`, as `type_` in `format`.
+    /// See `clipboard_append_typed()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///
+    ///     tk.clipboard_set_typed( "<b>bold</b>", "text/html", "STRING" )?;
+    ///     assert_eq!( tk.clipboard_get( "text/html" )?, "<b>bold</b>" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn clipboard_set_typed( &self, data: &str, type_: &str, format: &str ) -> InterpResult<()> {
+        self.clipboard_clear()?;
+        self.clipboard_append_typed( data, type_, format )
+    }
+
+    /// Retrieves the contents of the clipboard as `type_`, e.g. "STRING" or "text/html".
+    ///
+    /// Throws `ClipboardEmpty` if the clipboard holds nothing, or `UnsupportedSelectionType`
+    /// if it holds data but not of `type_`.
+    #[cex]
+    pub fn clipboard_get( &self, type_: &str ) -> Result!( String throws ClipboardEmpty, InterpError, UnsupportedSelectionType ) {
+        match self.eval(( "clipboard", "get", "-type", type_ )) {
+            Ok( data ) => Ok( data.to_string() ),
+            Err( err ) => {
+                let available = self.selection_targets( "CLIPBOARD" );
+                if available.is_empty() {
+                    throw!( ClipboardEmpty( err ));
+                }
+                if available.iter().any( |target| target == type_ ) {
+                    throw!( err );
+                }
+                throw!( UnsupportedSelectionType{ requested: type_.to_owned(), available });
+            },
+        }
+    }
+
+    /// Retrieves the contents of the clipboard as type "STRING".
+    #[cex]
+    pub fn clipboard_get_text( &self ) -> Result!( String throws ClipboardEmpty, InterpError, UnsupportedSelectionType ) {
+        ret!( self.clipboard_get( "STRING" ))
+    }
+}
+
@@ -81,12 +81,13 @@ pub(crate) const WIDGET_NAMES : [&'static str; 36] = [
 
 pub(crate) fn find_widget_name( name: &str ) -> Option<&'static str> {
     WIDGET_NAMES.iter().find( |&widget_name| widget_name == &name ).copied()
+        .or_else( || crate::megawidget::find_megawidget_command( name ))
 }
 
 /// The "base class" to which all Tk widgets `deref()`.
 #[derive( Copy, Clone )]
 pub struct Widget<Inst:TkInstance> {
-    pub(crate) path : &'static str,
+    pub(crate) path : WidgetPath,
     pub(crate) inst : Inst,
     pub(crate) mark : NotSendSync,
 }
@@ -100,42 +101,48 @@ impl<Inst:TkInstance> From<Widget<Inst>> for Obj {
 impl<Inst:TkInstance> Widget<Inst> {
     pub(crate) fn tk( &self ) -> Tk<Inst> { Tk::from_inst( self.inst )}
 
-    pub fn path( &self ) -> &'static str { self.path }
+    /// The widget path, e.g. ".frame.button".
+    pub fn path( &self ) -> String { self.path.to_string() }
 
     /// Reference to a Tk widget by its widget name.
     pub fn from_name( name: &str, inst: Inst ) -> InterpResult<Option<Self>> {
         let tk = Tk::from_inst( inst );
         let widget_exists = tk.eval(( "winfo", "exists", name ))?;
         if tk.boolean( widget_exists )? {
-            Ok( Some( Widget{ path: Tk::<Inst>::make_or_get_path( name ), inst, mark: NOT_SEND_SYNC }))
+            Ok( Some( Widget::from_tracked_path( &tk, name )))
         } else {
             Ok( None )
         }
     }
 
     pub(crate) fn from_name_unchecked( name: &str, inst: Inst ) -> Self {
-        Widget{ path: Tk::<Inst>::make_or_get_path( name ), inst, mark: NOT_SEND_SYNC }
+        Widget::from_tracked_path( &Tk::from_inst( inst ), name )
     }
 
     pub(crate) fn do_configure<Opts>( &self, opts: PathOptsWidgets<Opts,()> ) -> InterpResult<()>
         where Opts: IntoHomoTuple<OptPair>
     {
+        self.check_alive()?;
         let mut command = Vec::<Obj>::with_capacity( <<Opts as IntoHomoTuple<OptPair>>::Output as tuplex::Len>::LEN * 2 + 2 );
         command.push( self.path.into() );
         command.push( "configure".into() );
 
         append_opts( &mut command, opts.opts );
-        self.tk().run( command )
+        self.tk().run( &*command )?;
+        self.own_option_commands( &command[2..] );
+        Ok(())
     }
 
     pub(crate) fn cget( &self, name: &'static str ) -> InterpResult<Obj> {
+        self.check_alive()?;
         self.tk().eval(( self.path, "cget", name ))
     }
 
     pub(crate) fn add<Opts>( &self, name: &'static str, path_opts: PathOptsWidgets<Opts,()> ) -> InterpResult<Widget<Inst>>
         where Opts: IntoHomoTuple<OptPair>
     {
-        let path = self.tk().next_path( &self.path, path_opts.path );
+        self.check_alive()?;
+        let path = self.tk().next_path( &self.path.to_string(), path_opts.path );
         let mut command = Vec::<Obj>::with_capacity( <<Opts as IntoHomoTuple<OptPair>>::Output as tuplex::Len>::LEN * 2 + 2 );
         command.push( name.into() );
         command.push( path.as_str().into() );
@@ -143,7 +150,7 @@ impl<Inst:TkInstance> Widget<Inst> {
         append_opts( &mut command, path_opts.opts );
         self.tk().eval( command )?;
 
-        Ok( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC })
+        Ok( Widget::from_tracked_path( &self.tk(), &path ))
     }
 
     /// Adds child widget(s) containing hierachical trees of children widgets, including geometry managers.
@@ -196,7 +203,7 @@ impl<Inst:TkInstance> Widget<Inst> {
 
         let widgets = path_widgets.widgets.convert_tuple();
 
-        let mut created_widgets = CreatedWidgets::new( self.path );
+        let mut created_widgets = CreatedWidgets::new( &self.path.to_string() );
         let mut is_branch = false;
 
         DomForest::<(&'static str,&'static str),OptPair,Shape>::try_preorder( widgets, &mut |visit| -> InterpResult<()> {
@@ -849,11 +856,38 @@ macro_rules! def_widgets {
                 self.0.do_configure( opts.into() )
             }
 
-            pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+            /// Queries the value of an option, converted to the option's value type, e.g.
+            /// `String` for `text`, `Option<TkDistance>` for `width` and `Option<TkWidgetState>` for
+            /// `state`.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// use tcl::*;
+            /// use tk::*;
+            /// use tk::cmd::*;
+            ///
+            /// fn main() -> TkResult<()> {
+            ///     let tk = make_tk!()?;
+            ///     let button = tk.root().add_button( -text("Hello") -width(12) -state("disabled") )?;
+            ///
+            ///     assert_eq!( button.cget( text )?, "Hello" );
+            ///     assert_eq!( button.cget( width )?, Some( TkDistance::Pixels( 12.0 )));
+            ///     assert_eq!( button.cget( state )?, Some( TkWidgetState::Disabled ));
+            ///
+            ///     let frame = tk.root().add_frame( -width("2c") )?;
+            ///     assert_eq!( frame.cget( width )?, Some( TkDistance::Centimeters( 2.0 )));
+            ///
+            ///     Ok(())
+            /// }
+            /// ```
+            #[cex]
+            pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
                 where Opt : TkOption
                           + Into<opt::$widget_opt>
             {
-                self.0.cget( <Opt as TkOption>::NAME )
+                let obj = self.0.cget( <Opt as TkOption>::NAME )?;
+                ret!( crate::opt::option_value::<Opt>( obj ))
             }
 
             pub fn grab( &self ) -> InterpResult<()> {
@@ -938,6 +972,41 @@ def_widgets! {
     "ttk::treeview"    => TtkTreeview    TtkTreeviewTup    TtkTreeviewFn    AddTtkTreeview    add_ttk_treeview    TtkTreeviewOpt      ;
 }
 
+pub struct TkMegawidgetTup<Tup>( Tup );
+
+impl<Tup> Convert for TkMegawidgetTup<Tup> {
+    type Output = Tup;
+    fn convert( self ) -> Self::Output { self.0 }
+}
+
+def_hyphen_notation!( TkMegawidgetTup );
+
+/// Creates a megawidget of type `W` in `add_widgets()`, which must have been registered by
+/// `Tk::register_megawidget()`. See the `megawidget` module.
+pub fn megawidget<W,Opts,Widgs>( path_opts: impl Into<PathOptsWidgets<Opts,Widgs>> )
+    -> TkMegawidgetTup<
+        <
+            <Widgs as ConvertTuple>::Output as
+            PushFront<
+                heredom::Node<
+                    (&'static str,&'static str),
+                    <Opts as IntoHomoTuple<OptPair>>::Output
+                >
+            >
+        >
+        ::Output
+    >
+    where W    : crate::megawidget::Megawidget
+        , Widgs: ConvertTuple
+        , Opts : IntoHomoTuple<OptPair>
+        , <Widgs as ConvertTuple>::Output
+               : PushFront<heredom::Node<(&'static str,&'static str),<Opts as IntoHomoTuple<OptPair>>::Output>>
+{
+    let path_opts = path_opts.into();
+    let opts = <Opts as IntoHomoTuple<OptPair>>::into_homo_tuple( path_opts.opts );
+    TkMegawidgetTup( path_opts.widgets.convert_tuple().push_front( heredom::Node( (W::COMMAND, path_opts.path), opts )))
+}
+
 macro_rules! def_geometry_managers {
     ($($str:expr => $fn:ident $fn_cfg:ident $fn_forget:ident $fn_info:ident $ty:ident $trait:ident $slave_trait:ident $geoman_opt:ident;)+) => {$(
         pub struct $ty<Tup>( Tup );
@@ -1172,11 +1241,13 @@ impl<Inst:TkInstance> Tk<Inst> {
         self.run( command )
     }
 
-    pub fn busy_cget<Opt>( &self, widget: Widget<Inst>, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn busy_cget<Opt>( &self, widget: Widget<Inst>, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<opt::TkBusyHoldOpt>
     {
-        self.eval(( "tk", "busy", "cget", widget.path, <Opt as TkOption>::NAME ))
+        let obj = self.eval(( "tk", "busy", "cget", widget.path, <Opt as TkOption>::NAME ))?;
+        ret!( opt::option_value::<Opt>( obj ))
     }
 
     pub fn busy_configure<Opts>( &self, widget: Widget<Inst>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -1318,12 +1389,12 @@ impl<Inst:TkInstance> Tk<Inst> {
 
     pub fn focus_next( &self, widget: Widget<Inst> ) -> InterpResult<Widget<Inst>> {
         let path = self.eval(( "tk_focusNext", widget.path ))?.to_string();
-        Ok( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC })
+        Ok( Widget::from_tracked_path( self, &path ))
     }
 
     pub fn focus_prev( &self, widget: Widget<Inst> ) -> InterpResult<Widget<Inst>> {
         let path = self.eval(( "tk_focusPrev", widget.path ))?.to_string();
-        Ok( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC })
+        Ok( Widget::from_tracked_path( self, &path ))
     }
 
     pub fn focus_follows_mouse( &self ) -> InterpResult<()> {
@@ -1451,7 +1522,7 @@ impl<Inst:TkInstance> Tk<Inst> {
 
     pub fn grab_current( &self ) -> InterpResult<Widget<Inst>> {
         let path = self.eval(( "grab", "current" ))?.to_string();
-        Ok( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC })
+        Ok( Widget::from_tracked_path( self, &path ))
     }
 }
 
@@ -1530,7 +1601,7 @@ pub struct TkOptionMenu<Inst:TkInstance>( pub(crate) Widget<Inst> );
 impl<Inst:TkInstance> TkOptionMenu<Inst> {
     pub fn menu( &self ) -> InterpResult<TkMenu<Inst>> {
         let path = self.0.tk().eval( format!( "set rs_tk_widget_extra_data({})", self.0.path ))?.to_string();
-        Ok( TkMenu( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.0.inst, mark: NOT_SEND_SYNC }))
+        Ok( TkMenu( Widget::from_tracked_path( &self.0.tk(), &path )))
     }
 }
 
@@ -1576,10 +1647,10 @@ pub trait AddTkOptionMenu {
     fn add_option_menu<Inst:TkInstance>( &self, path_seg: &'static str, var_name: &str, items: &[&str] ) -> InterpResult<TkOptionMenu<Inst>>
         where Self: Deref<Target=Widget<Inst>>
     {
-        let path = self.tk().next_path( &self.path, path_seg );
+        let path = self.tk().next_path( &self.path.to_string(), path_seg );
         self.tk().eval(( "tk_rs_option_menu", path.as_str(), var_name, items ))?;
 
-        Ok( TkOptionMenu( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC }))
+        Ok( TkOptionMenu( Widget::from_tracked_path( &self.tk(), &path )))
     }
 }
 
@@ -26,6 +26,12 @@ pub use tcl::{
         NotDict,
         MutateSharedDict,
         InterpError,
+        Cancelled,
+        LimitExceeded,
+        UnknownEncoding,
+        InvalidSequence,
+        InvalidRegex,
+        ParseError,
     },
 };
 
@@ -40,6 +46,16 @@ macro_rules! impl_std_error_and_debug {
     };
 }
 
+/// The clipboard holds nothing.
+#[derive( Debug )]
+pub struct ClipboardEmpty( pub InterpError );
+impl_std_error!{ ClipboardEmpty }
+
+/// No window owns the selection.
+#[derive( Debug )]
+pub struct SelectionEmpty( pub InterpError );
+impl_std_error!{ SelectionEmpty }
+
 pub struct TagRangesNotInPair( pub Obj );
 impl_std_error_and_debug!( TagRangesNotInPair,
     "`pathName text tag ranges tagName` should return an even-length list, but returns {}." );
@@ -133,6 +149,16 @@ impl_std_error!{ TkSpinboxElementParseError }
 pub struct TtkStateParseError( pub String );
 impl_std_error!{ TtkStateParseError }
 
+/// The clipboard or selection does not hold data of the requested type.
+#[derive( Debug )]
+pub struct UnsupportedSelectionType {
+    /// The requested type, e.g. "STRING" or "text/html".
+    pub requested : String,
+    /// The types available, as listed by the "TARGETS" type.
+    pub available : Vec<String>,
+}
+impl_std_error!{ UnsupportedSelectionType }
+
 #[derive( Debug )]
 pub struct UnexpectedPanedwindowIdentifyResult( pub String );
 impl_std_error!{ UnexpectedPanedwindowIdentifyResult }
@@ -141,6 +167,20 @@ impl_std_error!{ UnexpectedPanedwindowIdentifyResult }
 pub struct UnexpectedScrollbarElementActivatedError( pub String );
 impl_std_error!{ UnexpectedScrollbarElementActivatedError }
 
+/// The window of a widget handle has been destroyed.
+#[derive( Debug )]
+pub struct WidgetDestroyed( pub String );
+impl_std_error!{ WidgetDestroyed }
+
+impl From<WidgetDestroyed> for InterpError {
+    fn from( err: WidgetDestroyed ) -> InterpError {
+        InterpError {
+            obj     : Obj::from( format!( "widget \"{}\" has been destroyed", err.0 )),
+            options : Obj::from(( "-code", "1", "-errorcode", Obj::from(( "TK", "WIDGET_DESTROYED", err.0 )))),
+        }
+    }
+}
+
 #[derive( Debug )]
 pub struct WidgetNotFound( pub String );
 impl_std_error!{ WidgetNotFound }
@@ -148,6 +188,8 @@ impl_std_error!{ WidgetNotFound }
 crate_error!{
     #[derive( Debug )]
     pub enum TkError {
+        ClipboardEmpty                          ,
+        SelectionEmpty                          ,
         TagRangesNotInPair                      ,
         TkAcceptableSizeParseError              ,
         TkButtonNoError                         ,
@@ -172,6 +214,8 @@ crate_error!{
         TtkStateParseError                      ,
         UnexpectedPanedwindowIdentifyResult     ,
         UnexpectedScrollbarElementActivatedError,
+        UnsupportedSelectionType                ,
+        WidgetDestroyed                         ,
         WidgetNotFound                          ,
 
         // errors from tcl crate
@@ -187,6 +231,12 @@ crate_error!{
         NotDict                                 ,
         MutateSharedDict                        ,
         InterpError                             ,
+        Cancelled                               ,
+        LimitExceeded                           ,
+        UnknownEncoding                         ,
+        InvalidSequence                         ,
+        InvalidRegex                            ,
+        ParseError                              ,
     }
 }
 
@@ -3,14 +3,22 @@
 /// 1. HBox: A frame containing two frames(so called west/east), placed horizontally and are resizable.
 ///
 /// 2. VBox: A frame containing two frames(so called north/south), placed vertically and are resizable.
+///
+/// 3. Splitter: A frame containing any number of panes, placed horizontally or vertically and are resizable, collapsible and keyboard-operable, with its layout serializable.
+///
+/// 4. ScrolledFrame: A frame whose contents scroll, with scrollbars shown only when needed.
 
 use crate::{
     InterpResult,
+    TkCanvas,
     TkInstance,
+    TkKey,
+    TkOrient,
     OptPair,
     PathOptsWidgets,
     TkResult,
     TtkFrame,
+    TtkScrollbar,
     Widget,
     cmd::*,
     event,
@@ -19,8 +27,13 @@ use crate::{
 };
 
 use std::{
+    cell::RefCell,
     ffi::c_int,
+    fmt,
+    num::ParseIntError,
     ops::Deref,
+    rc::Rc,
+    str::FromStr,
 };
 
 use tcl::*;
@@ -307,3 +320,801 @@ impl<Widg,Inst> AddVBox for Widg
         , Inst : TkInstance
 {
 }
+
+/// Thickness of the sashes of a `Splitter`, in pixels.
+const SASH_THICKNESS: c_int = 6;
+
+/// Distance a sash moves by a key press, in pixels.
+const SASH_KEY_STEP: c_int = 10;
+
+/// The virtual event generated on a `Splitter` when the user has moved a sash or collapsed
+/// or expanded a pane, e.g. to save its layout.
+pub const SPLITTER_CHANGED: &str = "SplitterChanged";
+
+/// Constraints of a pane in a `Splitter`.
+#[derive( Clone, Debug )]
+pub struct SplitterPane {
+    /// The share of the space gained or lost when the splitter resizes.
+    pub weight      : f64,
+    /// The minimum size in pixels, unless collapsed.
+    pub min         : c_int,
+    /// The maximum size in pixels, if any.
+    pub max         : Option<c_int>,
+    /// Whether double-clicking the adjacent sash collapses the pane.
+    pub collapsible : bool,
+}
+
+impl Default for SplitterPane {
+    fn default() -> Self {
+        SplitterPane{ weight: 1.0, min: 0, max: None, collapsible: true }
+    }
+}
+
+/// Sizes of the panes in a `Splitter`, for restoring a layout in later sessions.
+///
+/// It is written as the sizes separated by spaces, the size of a collapsed pane, which is restored
+/// on expanding, being prefixed with "-", e.g. "200 -120 300" or "200 -0 300".
+#[derive( Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize )]
+pub struct SplitterLayout {
+    pub sizes     : Vec<c_int>,
+    pub collapsed : Vec<bool>,
+}
+
+impl fmt::Display for SplitterLayout {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        for ( index, size ) in self.sizes.iter().enumerate() {
+            if index > 0 {
+                f.write_str( " " )?;
+            }
+            if self.collapsed.get( index ).copied().unwrap_or( false ) {
+                write!( f, "-{size}" )?;
+            } else {
+                write!( f, "{size}" )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SplitterLayout {
+    type Err = ParseIntError;
+
+    fn from_str( s: &str ) -> Result<Self, Self::Err> {
+        let mut layout = SplitterLayout::default();
+        for size in s.split_whitespace() {
+            let ( size, collapsed ) = match size.strip_prefix( '-' ) {
+                Some( size ) => ( size, true ),
+                None => ( size, false ),
+            };
+            layout.sizes.push( size.parse::<c_int>()?.max( 0 ));
+            layout.collapsed.push( collapsed );
+        }
+        Ok( layout )
+    }
+}
+
+struct SplitterState<Inst:TkInstance> {
+    frame     : TtkFrame<Inst>,
+    orient    : TkOrient,
+    specs     : Vec<SplitterPane>,
+    panes     : Vec<TtkFrame<Inst>>,
+    sashes    : Vec<TtkFrame<Inst>>,
+    sizes     : Vec<c_int>,
+    remembered: Vec<c_int>, // sizes of collapsed panes to restore
+    collapsed : Vec<bool>,
+    extent    : c_int,
+    drag_from : c_int,
+}
+
+impl<Inst:TkInstance> SplitterState<Inst> {
+    fn min( &self, index: usize ) -> c_int {
+        if self.collapsed[ index ] { 0 } else { self.specs[ index ].min }
+    }
+
+    fn max( &self, index: usize ) -> c_int {
+        if self.collapsed[ index ] { 0 } else { self.specs[ index ].max.unwrap_or( c_int::MAX )}
+    }
+
+    // The space for panes, excluding the sashes.
+    fn available( &self ) -> c_int {
+        self.extent - SASH_THICKNESS * self.sashes.len() as c_int
+    }
+
+    // Clamps the sizes into their limits, then grows or shrinks the panes by their weights to
+    // fill the available space.
+    fn fit( &mut self ) {
+        if self.extent <= 1 {
+            return; // not mapped yet
+        }
+        for index in 0..self.sizes.len() {
+            self.sizes[ index ] = self.sizes[ index ].max( self.min( index )).min( self.max( index ));
+        }
+        let available = self.available();
+        for _ in 0..self.sizes.len() {
+            let diff = available - self.sizes.iter().sum::<c_int>();
+            if diff == 0 {
+                break;
+            }
+            let eligible = ( 0..self.sizes.len() )
+                .filter( |&index| !self.collapsed[ index ] && if diff > 0 {
+                    self.sizes[ index ] < self.max( index )
+                } else {
+                    self.sizes[ index ] > self.min( index )
+                })
+                .collect::<Vec<_>>();
+            if eligible.is_empty() {
+                break;
+            }
+            let weight_sum = eligible.iter().map( |&index| self.specs[ index ].weight.max( 0.0 )).sum::<f64>();
+            let mut distributed = 0;
+            for ( nth, &index ) in eligible.iter().enumerate() {
+                let share = if nth + 1 == eligible.len() {
+                    diff - distributed
+                } else if weight_sum > 0.0 {
+                    ( diff as f64 * self.specs[ index ].weight.max( 0.0 ) / weight_sum ) as c_int
+                } else {
+                    diff / eligible.len() as c_int
+                };
+                let size = ( self.sizes[ index ] + share ).max( self.min( index )).min( self.max( index ));
+                distributed += size - self.sizes[ index ];
+                self.sizes[ index ] = size;
+            }
+            if distributed == 0 {
+                break;
+            }
+        }
+    }
+
+    fn place( &self ) -> InterpResult<()> {
+        let mut pos = 0;
+        for ( index, pane ) in self.panes.iter().enumerate() {
+            let size = self.sizes[ index ];
+            if self.collapsed[ index ] || size <= 0 {
+                pane.place_forget()?;
+            } else {
+                match self.orient {
+                    TkOrient::Horizontal => pane.place_configure( -x_(pos) -y_(0) -width(size) -relheight(1) )?,
+                    TkOrient::Vertical   => pane.place_configure( -x_(0) -y_(pos) -height(size) -relwidth(1) )?,
+                }
+            }
+            pos += size.max( 0 );
+            if let Some( sash ) = self.sashes.get( index ) {
+                match self.orient {
+                    TkOrient::Horizontal => sash.place_configure( -x_(pos) -y_(0) -width(SASH_THICKNESS) -relheight(1) )?,
+                    TkOrient::Vertical   => sash.place_configure( -x_(0) -y_(pos) -height(SASH_THICKNESS) -relwidth(1) )?,
+                }
+                pos += SASH_THICKNESS;
+            }
+        }
+        Ok(())
+    }
+
+    // Moves the sash between pane `index` and the next one by `delta` pixels as far as their
+    // limits allow, returning the distance moved.
+    fn move_sash( &mut self, index: usize, delta: c_int ) -> c_int {
+        let ( a, b ) = ( index, index+1 );
+        let ( size_a, size_b ) = ( self.sizes[a], self.sizes[b] );
+        let low  = ( self.min(a) - size_a ).max( size_b - self.max(b) );
+        let high = ( self.max(a) - size_a ).min( size_b - self.min(b) );
+        let delta = if low > high { 0 } else { delta.max( low ).min( high )};
+        self.sizes[a] += delta;
+        self.sizes[b] -= delta;
+        delta
+    }
+
+    fn neighbor( &self, index: usize ) -> Option<usize> {
+        ( index+1..self.panes.len() ).find( |&next| !self.collapsed[ next ])
+            .or_else( || ( 0..index ).rev().find( |&prev| !self.collapsed[ prev ]))
+    }
+
+    fn collapse( &mut self, index: usize ) {
+        if self.collapsed[ index ] {
+            return;
+        }
+        let size = self.sizes[ index ];
+        self.remembered[ index ] = size;
+        self.collapsed[ index ] = true;
+        self.sizes[ index ] = 0;
+        if let Some( neighbor ) = self.neighbor( index ) {
+            self.sizes[ neighbor ] += size;
+        }
+        self.fit();
+    }
+
+    fn expand( &mut self, index: usize ) {
+        if !self.collapsed[ index ] {
+            return;
+        }
+        self.collapsed[ index ] = false;
+        let size = self.remembered[ index ].max( self.specs[ index ].min );
+        self.sizes[ index ] = size;
+        if let Some( neighbor ) = self.neighbor( index ) {
+            self.sizes[ neighbor ] -= size;
+        }
+        self.fit();
+    }
+
+    // Expands a collapsed pane next to sash `index`, or collapses one of them.
+    fn toggle( &mut self, index: usize ) {
+        let ( a, b ) = ( index, index+1 );
+        if self.collapsed[a] {
+            self.expand(a);
+        } else if self.collapsed[b] {
+            self.expand(b);
+        } else if self.specs[a].collapsible {
+            self.collapse(a);
+        } else if self.specs[b].collapsible {
+            self.collapse(b);
+        }
+    }
+}
+
+/// A frame containing panes placed horizontally or vertically, separated by sashes which can be
+/// dragged, moved by arrow keys when focused, or double-clicked (or pressed Return on) to collapse
+/// or expand the adjacent panes.
+pub struct Splitter<Inst:TkInstance>( pub(crate) Widget<Inst>, Rc<RefCell<SplitterState<Inst>>> );
+
+impl<Inst:TkInstance> Clone for Splitter<Inst> {
+    fn clone( &self ) -> Self { Splitter( self.0, self.1.clone() )}
+}
+
+impl<Inst:TkInstance> Deref for Splitter<Inst> {
+    type Target = Widget<Inst>;
+
+    fn deref( &self ) -> &Self::Target { &self.0 }
+}
+
+impl<Inst:TkInstance> TkPackSlave  for Splitter<Inst> {}
+impl<Inst:TkInstance> TkGridSlave  for Splitter<Inst> {}
+impl<Inst:TkInstance> TkPlaceSlave for Splitter<Inst> {}
+
+impl<Inst:TkInstance> Splitter<Inst> {
+    /// Returns the number of panes.
+    pub fn len( &self ) -> usize { self.1.borrow().panes.len() }
+
+    /// Returns `true` if the splitter has no panes.
+    pub fn is_empty( &self ) -> bool { self.len() == 0 }
+
+    /// Returns the frame of the pane at `index`, counted from left or top.
+    pub fn pane( &self, index: usize ) -> Option<TtkFrame<Inst>> {
+        self.1.borrow().panes.get( index ).copied()
+    }
+
+    /// Returns the sizes of the panes in pixels, collapsed panes being 0.
+    pub fn sizes( &self ) -> Vec<c_int> { self.1.borrow().sizes.clone() }
+
+    /// Returns the positions of the sashes in pixels, from the left or top of the splitter.
+    pub fn sash_positions( &self ) -> Vec<c_int> {
+        let state = self.1.borrow();
+        let mut pos = 0;
+        state.sizes[ ..state.sizes.len().saturating_sub(1) ].iter()
+            .map( |size| { pos += size; let sash = pos; pos += SASH_THICKNESS; sash })
+            .collect()
+    }
+
+    /// Moves sash `index` to `pos`, as far as the limits of the adjacent panes allow.
+    pub fn set_sash_position( &self, index: usize, pos: c_int ) -> InterpResult<()> {
+        let current = self.sash_positions().get( index ).copied();
+        if let Some( current ) = current {
+            let mut state = self.1.borrow_mut();
+            state.move_sash( index, pos - current );
+            state.place()?;
+        }
+        Ok(())
+    }
+
+    /// Checks if the pane at `index` is collapsed.
+    pub fn is_collapsed( &self, index: usize ) -> bool {
+        self.1.borrow().collapsed.get( index ).copied().unwrap_or( false )
+    }
+
+    /// Collapses the pane at `index`, giving its space to an adjacent pane.
+    pub fn collapse( &self, index: usize ) -> InterpResult<()> {
+        let mut state = self.1.borrow_mut();
+        if index < state.panes.len() {
+            state.collapse( index );
+            state.place()?;
+        }
+        Ok(())
+    }
+
+    /// Expands the collapsed pane at `index` to its size before collapsing.
+    pub fn expand( &self, index: usize ) -> InterpResult<()> {
+        let mut state = self.1.borrow_mut();
+        if index < state.panes.len() {
+            state.expand( index );
+            state.place()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current layout, to be restored by `set_layout()`.
+    pub fn layout( &self ) -> SplitterLayout {
+        let state = self.1.borrow();
+        SplitterLayout {
+            sizes: ( 0..state.sizes.len() )
+                .map( |index| if state.collapsed[ index ] { state.remembered[ index ]} else { state.sizes[ index ]})
+                .collect(),
+            collapsed: state.collapsed.clone(),
+        }
+    }
+
+    /// Restores a layout returned by `layout()`, possibly in a previous session. The sizes are
+    /// adjusted to fit the splitter, and panes missing in `layout` are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let root = tk.root();
+    ///
+    ///     let panes = [ SplitterPane{ min: 50, ..Default::default() }, SplitterPane::default(), SplitterPane::default() ];
+    ///     let splitter = root.add_splitter( TkOrient::Horizontal, &panes, "splitter" -width(600) -height(400) )?
+    ///         .pack( -fill("both") -expand(true) )?;
+    ///     splitter.pane(0).unwrap().add_ttk_label( -text("files") )?.pack(())?;
+    ///     splitter.bind_fn( event::configure(), tclosure!( tk, || -> TkResult<()> { Ok(()) }))?;
+    ///
+    ///     splitter.set_layout( &"150 -100 200".parse().unwrap() )?;
+    ///     tk.update()?;
+    ///     assert!( splitter.is_collapsed(1) );
+    ///
+    ///     splitter.expand(1)?;
+    ///     assert_eq!( splitter.sizes()[1], 100 );
+    ///
+    ///     let saved = splitter.layout().to_string();
+    ///     assert_eq!( saved.parse::<SplitterLayout>().unwrap(), splitter.layout() );
+    ///     assert_eq!( "150 -0 200".parse::<SplitterLayout>().unwrap().to_string(), "150 -0 200" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_layout( &self, layout: &SplitterLayout ) -> InterpResult<()> {
+        let mut state = self.1.borrow_mut();
+        let len = state.panes.len().min( layout.sizes.len() );
+        for index in 0..len {
+            let collapsed = layout.collapsed.get( index ).copied().unwrap_or( false );
+            state.collapsed[ index ] = collapsed;
+            state.remembered[ index ] = layout.sizes[ index ];
+            state.sizes[ index ] = if collapsed { 0 } else { layout.sizes[ index ]};
+        }
+        state.fit();
+        state.place()
+    }
+
+    // Handles the events of the sash at `index`.
+    fn bind_sash( &self, index: usize ) -> InterpResult<()> {
+        let tk = self.0.tk();
+        let state = self.1.clone();
+        let ( sash, splitter, orient ) = {
+            let state = state.borrow();
+            ( state.sashes[ index ], state.frame, state.orient )
+        };
+        let root_coord = match orient {
+            TkOrient::Horizontal => "%X",
+            TkOrient::Vertical   => "%Y",
+        };
+
+        // Runs `f` on the state, then notifies of the change if the sash has moved.
+        let change = {
+            let state = state.clone();
+            move |f: &dyn Fn( &mut SplitterState<Inst> ) -> bool| -> InterpResult<()> {
+                let changed = {
+                    let mut state = state.borrow_mut();
+                    let changed = f( &mut state );
+                    if changed {
+                        state.place()?;
+                    }
+                    changed
+                };
+                if changed {
+                    tk.run(( "event", "generate", splitter, format!( "<<{SPLITTER_CHANGED}>>" )))?;
+                }
+                Ok(())
+            }
+        };
+        let change = Rc::new( change );
+
+        let drag_state = state.clone();
+        sash.bind_private_fn( event::button_press_1(), tclosure!( tk, args: root_coord, move |coord: c_int| -> TkResult<()> {
+            drag_state.borrow_mut().drag_from = coord;
+            Ok( tk.run(( "focus", sash ))? )
+        }))?;
+
+        let drag = change.clone();
+        sash.bind_private_fn( event::button_1().motion(), tclosure!( tk, args: root_coord, move |coord: c_int| -> TkResult<()> {
+            Ok( drag( &|state| {
+                let moved = state.move_sash( index, coord - state.drag_from );
+                state.drag_from += moved;
+                moved != 0
+            })? )
+        }))?;
+
+        let toggle = change.clone();
+        sash.bind_private_fn( event::double().button_press_1(), tclosure!( tk, move || -> InterpResult<()> {
+            toggle( &|state| { state.toggle( index ); true })
+        }))?;
+
+        let toggle = change.clone();
+        sash.bind_private_fn( event::key_press( TkKey::Return ), tclosure!( tk, move || -> InterpResult<()> {
+            toggle( &|state| { state.toggle( index ); true })
+        }))?;
+
+        let ( backward, forward ) = match orient {
+            TkOrient::Horizontal => ( TkKey::Left, TkKey::Right ),
+            TkOrient::Vertical   => ( TkKey::Up,   TkKey::Down  ),
+        };
+        for ( key, step ) in [ ( backward, -SASH_KEY_STEP ), ( forward, SASH_KEY_STEP )] {
+            let step_sash = change.clone();
+            sash.bind_private_fn( event::key_press( key ), tclosure!( tk, move || -> InterpResult<()> {
+                step_sash( &|state| state.move_sash( index, step ) != 0 )
+            }))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait AddSplitter {
+    /// Adds a splitter of `panes` placed in `orient`ation. The panes share the space by their
+    /// weights until the sashes are moved.
+    fn add_splitter<Opts,Inst:TkInstance>(
+        &self,
+        orient    : TkOrient,
+        panes     : &[SplitterPane],
+        path_opts : impl Into<PathOptsWidgets<Opts,()>>
+    ) -> InterpResult<Splitter<Inst>>
+        where Self : Sized
+                   + Deref<Target=Widget<Inst>>
+            , Opts : IntoHomoTuple<opt::TtkFrameOpt>
+                   + IntoHomoTuple<OptPair>
+    {
+        let tk = self.deref().tk();
+        let frame = self.add_ttk_frame( path_opts )?;
+
+        let mut pane_frames = Vec::with_capacity( panes.len() );
+        let mut sashes = Vec::with_capacity( panes.len().saturating_sub(1) );
+        let sash_cursor = match orient {
+            TkOrient::Horizontal => "sb_h_double_arrow",
+            TkOrient::Vertical   => "sb_v_double_arrow",
+        };
+        for index in 0..panes.len() {
+            pane_frames.push( frame.add_ttk_frame( no_arg() )? );
+            if index + 1 < panes.len() {
+                sashes.push( frame.add_ttk_frame( -takefocus(1) -relief("raised") -borderwidth(1) -cursor( sash_cursor ))? );
+            }
+        }
+
+        let state = Rc::new( RefCell::new( SplitterState {
+            frame,
+            orient,
+            specs      : panes.to_vec(),
+            panes      : pane_frames,
+            sashes,
+            sizes      : vec![ 0; panes.len() ],
+            remembered : vec![ 0; panes.len() ],
+            collapsed  : vec![ false; panes.len() ],
+            extent     : 0,
+            drag_from  : 0,
+        }));
+        let splitter = Splitter( frame.0, state.clone() );
+
+        for index in 0..panes.len().saturating_sub(1) {
+            splitter.bind_sash( index )?;
+        }
+
+        frame.bind_private_fn( event::configure(), tclosure!( tk, args: "%w %h", move |width: c_int, height: c_int| -> TkResult<()> {
+            let mut state = state.borrow_mut();
+            state.extent = match state.orient {
+                TkOrient::Horizontal => width,
+                TkOrient::Vertical   => height,
+            };
+            state.fit();
+            Ok( state.place()? )
+        }))?;
+
+        Ok( splitter )
+    }
+}
+
+impl<Widg,Inst> AddSplitter for Widg
+    where Widg : Deref<Target=Widget<Inst>>
+        , Inst : TkInstance
+{
+}
+
+// The tag of the canvas item holding the interior of a `ScrolledFrame`.
+const SCROLLED_INTERIOR_TAG: &str = "interior";
+
+/// A frame whose contents scroll, with scrollbars shown only when needed and scrolled by the
+/// mouse wheel while the pointer is over it.
+///
+/// It dereferences to its outer frame, containing the canvas and the scrollbars, so that e.g.
+/// `pack()`, `pack_forget()` and `destroy()` act on the whole. Children are added to the frame
+/// returned by `interior()`, by `add_widgets()` or `add_*()` as to any other container.
+///
+/// # Example
+///
+/// ```rust
+/// use tcl::*;
+/// use tk::*;
+/// use tk::cmd::*;
+///
+/// fn main() -> TkResult<()> {
+///     let tk = make_tk!()?;
+///     let root = tk.root();
+///
+///     let scrolled = root.add_scrolled_frame( "scrolled" -height(200) )?
+///         .pack( -fill("both") -expand(true) )?;
+///     scrolled.interior().bind_fn( event::configure(), tclosure!( tk, || -> TkResult<()> { Ok(()) }))?;
+///
+///     let created = scrolled.interior().add_widgets(
+///         -pack( -fill("x")
+///             -ttk_label( "first" -text("first") )
+///             -text( "log" -height(40) )
+///             -ttk_label( "last" -text("last") )
+///         )
+///     )?;
+///
+///     let last = created.query::<TtkLabel<_>>( "last" ).unwrap();
+///     tk.update()?;
+///     scrolled.see( &last )?;
+///
+///     tk.destroy(( scrolled, ))?;
+///     assert!( !scrolled.interior().is_alive() );
+///
+///     Ok(())
+/// }
+/// ```
+#[derive( Copy, Clone )]
+pub struct ScrolledFrame<Inst:TkInstance>( pub(crate) Widget<Inst> );
+
+impl<Inst:TkInstance> Deref for ScrolledFrame<Inst> {
+    type Target = Widget<Inst>;
+
+    fn deref( &self ) -> &Self::Target { &self.0 }
+}
+
+impl<Inst:TkInstance> From<ScrolledFrame<Inst>> for Obj {
+    fn from( scrolled: ScrolledFrame<Inst> ) -> Obj { Obj::from( scrolled.0 )}
+}
+
+impl<Inst:TkInstance> TkPackSlave for ScrolledFrame<Inst> {
+    fn pack<Opts,I:TkInstance>( self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Self>
+        where Self : Deref<Target=Widget<I>>
+            , Opts : IntoHomoTuple<opt::TkPackOpt>
+                   + IntoHomoTuple<OptPair>
+    {
+        self.outer().pack( opts )?;
+        Ok( self )
+    }
+}
+
+impl<Inst:TkInstance> TkGridSlave for ScrolledFrame<Inst> {
+    fn grid<Opts,I:TkInstance>( self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Self>
+        where Self : Deref<Target=Widget<I>>
+            , Opts : IntoHomoTuple<opt::TkGridOpt>
+                   + IntoHomoTuple<OptPair>
+    {
+        self.outer().grid( opts )?;
+        Ok( self )
+    }
+}
+
+impl<Inst:TkInstance> TkPlaceSlave for ScrolledFrame<Inst> {
+    fn place<Opts,I:TkInstance>( self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Self>
+        where Self : Deref<Target=Widget<I>>
+            , Opts : IntoHomoTuple<opt::TkPlaceOpt>
+                   + IntoHomoTuple<OptPair>
+    {
+        self.outer().place( opts )?;
+        Ok( self )
+    }
+}
+
+impl<Inst:TkInstance> ScrolledFrame<Inst> {
+    /// Returns the frame containing the canvas and the scrollbars.
+    pub fn outer( &self ) -> TtkFrame<Inst> {
+        TtkFrame( self.0 )
+    }
+
+    /// Returns the canvas scrolling the interior frame.
+    pub fn canvas( &self ) -> TkCanvas<Inst> {
+        let path = format!( "{}.canvas", self.0.path );
+        TkCanvas( Widget::from_name_unchecked( &path, self.0.inst ))
+    }
+
+    /// Returns the frame containing the scrolled widgets.
+    pub fn interior( &self ) -> TtkFrame<Inst> {
+        let path = format!( "{}.canvas.interior", self.0.path );
+        TtkFrame( Widget::from_name_unchecked( &path, self.0.inst ))
+    }
+
+    /// Returns the horizontal scrollbar.
+    pub fn xscrollbar( &self ) -> TtkScrollbar<Inst> {
+        let path = format!( "{}.xbar", self.outer().path );
+        TtkScrollbar( Widget::from_name_unchecked( &path, self.0.inst ))
+    }
+
+    /// Returns the vertical scrollbar.
+    pub fn yscrollbar( &self ) -> TtkScrollbar<Inst> {
+        let path = format!( "{}.ybar", self.outer().path );
+        TtkScrollbar( Widget::from_name_unchecked( &path, self.0.inst ))
+    }
+
+    /// Scrolls the least distance making `widget`, a descendant of the interior, visible. If it
+    /// is larger than the view, its top-left part is shown.
+    pub fn see( &self, widget: &Widget<Inst> ) -> InterpResult<()> {
+        let tk = self.0.tk();
+        tk.run(( "update", "idletasks" ))?;
+        let canvas = self.canvas();
+        let interior = self.interior();
+        let int = |script: Obj| -> InterpResult<c_int> { tk.int( tk.eval( script )? ) };
+
+        for ( root, size, canvas_coord, view ) in [ ( "rootx", "width", "canvasx", "xview" ), ( "rooty", "height", "canvasy", "yview" )] {
+            let offset  = int( Obj::from(( "winfo", root, widget.path )))? - int( Obj::from(( "winfo", root, interior.path )))?;
+            let extent  = int( Obj::from(( "winfo", size, widget.path )))?;
+            let total   = int( Obj::from(( "winfo", size, interior.path )))?;
+            let visible = int( Obj::from(( "winfo", size, canvas.path )))?;
+            let first   = tk.double( tk.eval(( canvas.path, canvas_coord, 0 ))? )? as c_int;
+
+            let target = if offset < first {
+                Some( offset )
+            } else if offset + extent > first + visible {
+                Some(( offset + extent - visible ).min( offset ))
+            } else {
+                None
+            };
+            if let Some( target ) = target {
+                if total > 0 {
+                    tk.run(( canvas.path, view, "moveto", target as f64 / total as f64 ))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Makes the interior as wide as the canvas unless it requests more, and updates the
+    // scrolled region to its requested size.
+    fn relayout( &self ) -> TkResult<()> {
+        let tk = self.0.tk();
+        let canvas = self.canvas();
+        let interior = self.interior();
+        let canvas_width = tk.int( tk.eval(( "winfo", "width", canvas.path ))? )?;
+        let req_width  = tk.int( tk.eval(( "winfo", "reqwidth",  interior.path ))? )?;
+        let req_height = tk.int( tk.eval(( "winfo", "reqheight", interior.path ))? )?;
+
+        // a width of 0 lets the interior take the width it requests
+        let width = if req_width < canvas_width { canvas_width } else { 0 };
+        tk.run(( canvas.path, "itemconfigure", SCROLLED_INTERIOR_TAG, "-width", width ))?;
+        tk.run(( canvas.path, "configure", "-scrollregion", Obj::from(( 0, 0, req_width.max( canvas_width ), req_height ))))?;
+        self.tag_wheel()
+    }
+
+    // The bindtag routing the mouse wheel to this frame.
+    fn wheel_tag( &self ) -> String {
+        format!( "tk_rs_wheel{}", self.0.path )
+    }
+
+    // Adds the bindtag of the mouse wheel, before "all", to the canvas and the descendants of
+    // the interior lacking it, e.g. the ones added since last time.
+    fn tag_wheel( &self ) -> TkResult<()> {
+        let tk = self.0.tk();
+        let wheel_tag = self.wheel_tag();
+        let mut paths = vec![ self.canvas().path.to_string() ];
+        while let Some( path ) = paths.pop() {
+            let mut tags = tk.eval(( "bindtags", path.as_str() ))?
+                .get_elements()?
+                .map( |tag| tag.to_string() )
+                .collect::<Vec<_>>();
+            if !tags.contains( &wheel_tag ) {
+                let all = tags.iter().position( |tag| tag == "all" ).unwrap_or( tags.len() );
+                tags.insert( all, wheel_tag.clone() );
+                tk.run(( "bindtags", path.as_str(), tags ))?;
+            }
+            paths.extend( tk.eval(( "winfo", "children", path.as_str() ))?
+                .get_elements()?
+                .map( |child| child.to_string() ));
+        }
+        Ok(())
+    }
+
+    // Routes the mouse wheel to this frame while the pointer is over it, by the bindtag of its
+    // widgets, which is given to the ones added later on entering or resizing.
+    fn bind_wheel( &self ) -> InterpResult<()> {
+        let tk = self.0.tk();
+        let outer = self.outer();
+        let canvas = self.canvas();
+
+        let wheel = outer.command( tclosure!( tk, move |view: String, delta: c_int| -> TkResult<()> {
+            let units = if delta.abs() >= 120 { -delta / 120 } else { -delta };
+            Ok( tk.run(( canvas.path, view, "scroll", units, "units" ))? )
+        }))?;
+        let wheel = wheel.trim().to_owned();
+
+        let wheel_tag = self.wheel_tag();
+        let bindings = [
+            ( "<Button-4>",         format!( "catch {{{wheel} yview 120}}" )),
+            ( "<Button-5>",         format!( "catch {{{wheel} yview -120}}" )),
+            ( "<Shift-Button-4>",   format!( "catch {{{wheel} xview 120}}" )),
+            ( "<Shift-Button-5>",   format!( "catch {{{wheel} xview -120}}" )),
+            ( "<MouseWheel>",       format!( "catch {{{wheel} yview %D}}" )),
+            ( "<Shift-MouseWheel>", format!( "catch {{{wheel} xview %D}}" )),
+        ];
+        for ( sequence, script ) in &bindings {
+            tk.run(( "bind", wheel_tag.as_str(), *sequence, script.as_str() ))?;
+        }
+        outer.own_bindtag( &wheel_tag );
+
+        let scrolled = *self;
+        outer.bind_private_fn( event::enter(), tclosure!( tk, move || -> TkResult<()> {
+            scrolled.tag_wheel()
+        }))
+    }
+}
+
+pub trait AddScrolledFrame {
+    /// Adds a `ScrolledFrame`. The options apply to its outer frame.
+    fn add_scrolled_frame<Opts,Inst:TkInstance>( &self, path_opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<ScrolledFrame<Inst>>
+        where Self : Sized
+                   + Deref<Target=Widget<Inst>>
+            , Opts : IntoHomoTuple<opt::TtkFrameOpt>
+                   + IntoHomoTuple<OptPair>
+    {
+        let tk = self.deref().tk();
+
+        let outer = self.add_ttk_frame( path_opts )?;
+        // Scrolling is not restricted to increments, which would keep `see()` from being exact.
+        let canvas = outer.add_canvas( "canvas" -highlightthickness(0) -borderwidth(0) )?
+            .grid( -row(0) -column(0) -sticky("nsew") )?;
+        let xbar = outer.add_ttk_scrollbar( "xbar" -orient("horizontal") -command( format!( "{} xview", canvas.path )))?
+            .grid( -row(1) -column(0) -sticky("ew") )?;
+        let ybar = outer.add_ttk_scrollbar( "ybar" -orient("vertical") -command( format!( "{} yview", canvas.path )))?
+            .grid( -row(0) -column(1) -sticky("ns") )?;
+        tk.run(( "grid", "rowconfigure",    outer.path, 0, "-weight", 1 ))?;
+        tk.run(( "grid", "columnconfigure", outer.path, 0, "-weight", 1 ))?;
+
+        let interior = canvas.add_ttk_frame( "interior" )?;
+        tk.run(( canvas.path, "create", "window", 0, 0, "-anchor", "nw", "-window", interior.path, "-tags", SCROLLED_INTERIOR_TAG ))?;
+        let scrolled = ScrolledFrame( outer.0 );
+
+        // Hides a scrollbar when all is visible.
+        let xscroll = canvas.command( tclosure!( tk, move |first: f64, last: f64| -> TkResult<()> {
+            if first <= 0.0 && last >= 1.0 {
+                tk.run(( "grid", "remove", xbar.path ))?;
+            } else {
+                tk.run(( "grid", xbar.path ))?;
+            }
+            Ok( tk.run(( xbar.path, "set", first, last ))? )
+        }))?;
+        let yscroll = canvas.command( tclosure!( tk, move |first: f64, last: f64| -> TkResult<()> {
+            if first <= 0.0 && last >= 1.0 {
+                tk.run(( "grid", "remove", ybar.path ))?;
+            } else {
+                tk.run(( "grid", ybar.path ))?;
+            }
+            Ok( tk.run(( ybar.path, "set", first, last ))? )
+        }))?;
+        canvas.configure( -xscrollcommand( xscroll.as_str() ) -yscrollcommand( yscroll.as_str() ))?;
+
+        canvas.bind_private_fn( event::configure(), tclosure!( tk, move || -> TkResult<()> {
+            scrolled.relayout()
+        }))?;
+        interior.bind_private_fn( event::configure(), tclosure!( tk, move || -> TkResult<()> {
+            scrolled.relayout()
+        }))?;
+
+        scrolled.bind_wheel()?;
+        Ok( scrolled )
+    }
+}
+
+impl<Widg,Inst> AddScrolledFrame for Widg
+    where Widg : Deref<Target=Widget<Inst>>
+        , Inst : TkInstance
+{
+}
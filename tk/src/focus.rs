@@ -1,6 +1,5 @@
 use crate::{
     InterpResult,
-    NOT_SEND_SYNC,
     Tk,
     TkInstance,
     Widget,
@@ -10,7 +9,7 @@ use tcl::Obj;
 
 impl<Inst:TkInstance> Tk<Inst> {
     pub fn focus( &self ) -> InterpResult<Widget<Inst>> {
-        Ok( Widget{ path: Tk::<Inst>::make_or_get_path( &self.eval( "focus" )?.to_string() ), inst: self.inst, mark: NOT_SEND_SYNC })
+        Ok( Widget::from_tracked_path( self, &self.eval( "focus" )?.to_string() ))
     }
 }
 
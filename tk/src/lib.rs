@@ -119,7 +119,6 @@ use once_cell::sync::Lazy;
 pub(crate) use std::{
     any::TypeId,
     cell::{Cell, RefCell},
-    collections::HashSet,
     marker::PhantomData,
     mem,
     ops::Deref,
@@ -169,8 +168,14 @@ pub(crate) use cmd::{
 pub mod key_syms;
 pub use key_syms::TkKey;
 
+mod lifecycle;
+pub(crate) use lifecycle::WidgetPath;
+
 mod lower;
 
+pub mod megawidget;
+pub use megawidget::{AddMegawidget, Mega, Megawidget};
+
 #[macro_use]
 pub mod opt;
 use opt::{OptPair, TkOption};
@@ -185,6 +190,10 @@ pub use query::{UpcastFrom, UpcastableWidget, CreatedWidgets};
 
 mod raise;
 
+mod selection;
+
+pub mod state;
+
 pub mod ttk_style;
 
 pub mod ttk_widget;
@@ -204,6 +213,7 @@ pub use image::Image;
 
 mod grid;
 mod pack;
+mod clipboard;
 mod focus;
 mod winfo;
 
@@ -224,22 +234,32 @@ pub use traits::{Delete, TkBBoxTrait, TkEntryTraits, TkXView, TkXViewIndex, TkYV
 
 pub mod types;
 pub use types::{
+    TkAnchor,
     TkBBox,
     TkColor,
+    TkCompound,
     TkCoord,
+    TkCursor,
+    TkFill,
     TkHandler,
     TkGeometry,
     TkDistance,
     TkDLine,
+    TkJustify,
+    TkOrient,
     TkRGB,
     TkRectangle,
+    TkRelief,
     TkResizable,
     TkRequester,
     TkState,
     TkSize,
     TkScreenName,
+    TkSticky,
     TkVisualClass,
+    TkWidgetState,
     TkWindowingSystem,
+    TkWrap,
     TtkInsertPos,
     TtkTreeviewRegion,
 };
@@ -385,7 +405,7 @@ pub mod font;
 pub use font::Font;
 
 pub mod ext;
-pub use ext::{AddHBox, AddVBox, HBox, HBoxResize, VBox, VBoxResize};
+pub use ext::{AddHBox, AddScrolledFrame, AddSplitter, AddVBox, HBox, HBoxResize, ScrolledFrame, Splitter, SplitterLayout, SplitterPane, VBox, VBoxResize};
 
 const TEST_MAIN_WINDOW: &'static str = "winfo exists .\0";
 
@@ -504,11 +524,6 @@ impl<T:'static + Copy + Clone> TkInstance for T {}
 thread_local! {
     static TK_INSTANCES: RefCell<Vec<(TypeId, Engine)>> = RefCell::new( Vec::new() );
 
-    static WIDGET_PATH_SET: RefCell<HashSet<&'static str>> = {
-        let mut set = HashSet::new();
-        set.insert( "." );
-        RefCell::new( set )
-    };
 }
 
 static MUTEX: Lazy<Mutex<()>> = Lazy::new( || Mutex::new(()) );
@@ -559,36 +574,19 @@ impl<Inst> Tk<Inst>
             instances.borrow_mut().push(( tk_type_id, engine ));
         });
 
+        tk.init_lifecycle();
+
         Ok( tk )
     }
 
     pub(crate) fn from_inst( inst: Inst ) -> Tk<Inst> { Tk{ inst, mark: NOT_SEND_SYNC }}
 
-    pub(crate) fn make_or_get_path( path: &str ) -> &'static str {
-        WIDGET_PATH_SET.with( |path_set| {
-            let mut path_set = path_set.borrow_mut();
-            match path_set.get( path ) {
-                Some( path ) => *path,
-                None => {
-                    let path: &'static str = Box::leak( path.to_owned().into_boxed_str() );
-                    path_set.insert( path );
-                    path
-                },
-            }
-        })
-    }
 }
 
 impl<Inst:TkInstance> Tk<Inst> {
     /// Reference to the root widget of tk. The root's widget path is ".".
     pub fn root( &self ) -> TkRoot<Inst> {
-        TkRoot(
-            Widget {
-                path : ".",
-                inst : self.inst,
-                mark : NOT_SEND_SYNC,
-            }
-        )
+        TkRoot( Widget::from_tracked_path( self, "." ))
     }
 
     pub(crate) fn next_path( &self, parent_path: &str, path: &str ) -> String {
@@ -0,0 +1,421 @@
+//! Lifecycle of widget handles.
+//!
+//! A widget handle refers to its window by a slot in a table of windows, along with the
+//! generation of the slot, which is bumped whenever the slot is reused. The slot of a destroyed
+//! window, holding its path, is reused by the next window, so creating and destroying widgets
+//! does not grow memory without bound, and a handle of a destroyed window can be told apart from
+//! a handle of a newer window at the same path.
+//!
+//! Destruction is noticed by a command trace on the widget command, so it works regardless
+//! of bindings and bindtags. A handle of a destroyed window converts to a name of no window,
+//! e.g. ".row (destroyed)", so that any command called on it fails instead of acting on a
+//! newer window.
+//!
+//! Commands created by `tclosure!()` can be owned by a widget, to be deleted along with it,
+//! freeing their closures.
+
+use crate::{
+    InterpResult,
+    NOT_SEND_SYNC,
+    Tk,
+    TkInstance,
+    TkResult,
+    TypeId,
+    Widget,
+    error::WidgetDestroyed,
+};
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::{self, Debug, Display},
+};
+
+use tcl::{
+    Obj,
+    tclosure,
+};
+
+// The name of the command notified of the deletion of widget commands.
+const DESTROY_TRACE: &str = "tk_rs_widget_destroyed";
+
+// What a handle converts to if its slot has been reused.
+const DESTROYED: &str = "(destroyed)";
+
+struct Slot {
+    path       : String,
+    generation : u32,
+    alive      : bool,
+    owned      : OwnedCommands,
+}
+
+#[derive( Default )]
+struct OwnedCommands {
+    commands : Vec<String>,
+    keyed    : HashMap<String, String>, // e.g. event sequence => command name
+    bindtags : Vec<String>, // bindtags whose bindings are removed along with the widget
+}
+
+impl OwnedCommands {
+    fn refers_to( &self, name: &str ) -> bool {
+        self.commands.iter().chain( self.keyed.values() ).any( |command| command == name )
+    }
+}
+
+thread_local! {
+    static SLOTS: RefCell<Vec<Slot>> = const { RefCell::new( Vec::new() )};
+
+    // Slots of destroyed windows, ready for reuse.
+    static FREE_SLOTS: RefCell<Vec<u32>> = const { RefCell::new( Vec::new() )};
+
+    // Slots of live windows, by path.
+    static LIVE_PATHS: RefCell<HashMap<TypeId, HashMap<String, u32>>> = RefCell::new( HashMap::new() );
+
+    // Serial numbers of widget commands renamed to be deleted later.
+    static RETIRED_COMMANDS: Cell<u64> = const { Cell::new( 0 )};
+}
+
+/// The path of a widget handle, which converts to e.g. ".frame.button" while the window lives.
+#[derive( Copy, Clone, PartialEq, Eq, Hash )]
+pub(crate) struct WidgetPath {
+    slot       : u32,
+    generation : u32,
+}
+
+impl WidgetPath {
+    // Calls `f` with the path if the window lives, otherwise with a name of no window.
+    fn with_str<R>( self, f: impl FnOnce( &str ) -> R ) -> R {
+        SLOTS.with( |slots| {
+            let slots = slots.borrow();
+            match slots.get( self.slot as usize ) {
+                Some( slot ) if slot.generation == self.generation => {
+                    if slot.alive {
+                        f( &slot.path )
+                    } else {
+                        f( &format!( "{} {}", slot.path, DESTROYED ))
+                    }
+                },
+                _ => f( DESTROYED ),
+            }
+        })
+    }
+
+    fn is_alive( self ) -> bool {
+        SLOTS.with( |slots| slots.borrow()
+            .get( self.slot as usize )
+            .is_some_and( |slot| slot.generation == self.generation && slot.alive ))
+    }
+
+    // The path of the window, even if destroyed, as long as the slot has not been reused.
+    fn last_known( self ) -> String {
+        SLOTS.with( |slots| slots.borrow()
+            .get( self.slot as usize )
+            .filter( |slot| slot.generation == self.generation )
+            .map( |slot| slot.path.clone() )
+            .unwrap_or_else( || DESTROYED.to_owned() ))
+    }
+
+    fn with_owned<R>( self, f: impl FnOnce( &mut OwnedCommands ) -> R ) -> Option<R> {
+        SLOTS.with( |slots| slots.borrow_mut()
+            .get_mut( self.slot as usize )
+            .filter( |slot| slot.generation == self.generation && slot.alive )
+            .map( |slot| f( &mut slot.owned )))
+    }
+}
+
+impl Display for WidgetPath {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        self.with_str( |path| f.write_str( path ))
+    }
+}
+
+impl Debug for WidgetPath {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        Display::fmt( self, f )
+    }
+}
+
+impl From<WidgetPath> for Obj {
+    fn from( path: WidgetPath ) -> Obj {
+        path.with_str( |path| Obj::from( path ))
+    }
+}
+
+impl PartialEq<str> for WidgetPath {
+    fn eq( &self, other: &str ) -> bool {
+        self.with_str( |path| path == other )
+    }
+}
+
+impl PartialEq<String> for WidgetPath {
+    fn eq( &self, other: &String ) -> bool {
+        self.with_str( |path| path == other )
+    }
+}
+
+impl PartialEq<&str> for WidgetPath {
+    fn eq( &self, other: &&str ) -> bool {
+        self.with_str( |path| path == *other )
+    }
+}
+
+// The command name of a script returned by `tclosure!()`, which may have arguments appended.
+fn command_name( script: &str ) -> String {
+    script.split_whitespace().next().unwrap_or_default().to_owned()
+}
+
+impl<Inst:TkInstance> Tk<Inst> {
+    // Defines the command traced on deletion of widget commands.
+    pub(crate) fn init_lifecycle( &self ) {
+        let tk = *self;
+        let _ = tclosure!( self, cmd: DESTROY_TRACE,
+            move |old_name: String, _new_name: String, _op: String| -> TkResult<()> {
+                tk.release_path( old_name.strip_prefix( "::" ).unwrap_or( &old_name ));
+                Ok(())
+            }
+        );
+    }
+
+    fn live_slot( path: &str ) -> Option<u32> {
+        LIVE_PATHS.with( |live| live.borrow()
+            .get( &TypeId::of::<Inst>() )
+            .and_then( |paths| paths.get( path ))
+            .copied() )
+    }
+
+    // Returns the path of an existing window, taking a slot for it if it is not tracked yet.
+    pub(crate) fn track_path( &self, path: &str ) -> WidgetPath {
+        if let Some( slot ) = Tk::<Inst>::live_slot( path ) {
+            let generation = SLOTS.with( |slots| slots.borrow()[ slot as usize ].generation );
+            return WidgetPath{ slot, generation };
+        }
+
+        let reused = FREE_SLOTS.with( |free| free.borrow_mut().pop() );
+        let widget_path = SLOTS.with( |slots| {
+            let mut slots = slots.borrow_mut();
+            let slot = match reused {
+                Some( slot ) => {
+                    let entry = &mut slots[ slot as usize ];
+                    entry.path = path.to_owned();
+                    entry.generation = entry.generation.wrapping_add( 1 );
+                    entry.alive = true;
+                    slot
+                },
+                None => {
+                    slots.push( Slot{ path: path.to_owned(), generation: 0, alive: true, owned: OwnedCommands::default() });
+                    ( slots.len() - 1 ) as u32
+                },
+            };
+            WidgetPath{ slot, generation: slots[ slot as usize ].generation }
+        });
+        LIVE_PATHS.with( |live| live.borrow_mut()
+            .entry( TypeId::of::<Inst>() )
+            .or_default()
+            .insert( path.to_owned(), widget_path.slot ));
+
+        if self.run(( "trace", "add", "command", path, "delete", DESTROY_TRACE )).is_err() {
+            // no such window, so no trace
+            self.release_path( path );
+        }
+        widget_path
+    }
+
+    // Marks the window at `path` as destroyed, deletes the commands it owns and frees its slot.
+    fn release_path( &self, path: &str ) {
+        let slot = LIVE_PATHS.with( |live| live.borrow_mut()
+            .get_mut( &TypeId::of::<Inst>() )
+            .and_then( |paths| paths.remove( path )));
+        if let Some( slot ) = slot {
+            let owned = SLOTS.with( |slots| {
+                let entry = &mut slots.borrow_mut()[ slot as usize ];
+                entry.alive = false;
+                std::mem::take( &mut entry.owned )
+            });
+            FREE_SLOTS.with( |free| free.borrow_mut().push( slot ));
+            for tag in &owned.bindtags {
+                self.unbind_tag( tag );
+            }
+            self.delete_commands_later( owned.commands.into_iter().chain( owned.keyed.into_values() ));
+        }
+    }
+
+    // Removes all bindings of `tag`, which are not freed by Tk as long as the interpreter lives.
+    fn unbind_tag( &self, tag: &str ) {
+        let sequences = self.eval(( "bind", tag ))
+            .ok()
+            .and_then( |sequences| sequences.get_elements().ok() );
+        for sequence in sequences.into_iter().flatten() {
+            self.run(( "bind", tag, sequence, "" )).ok();
+        }
+    }
+
+    // Releases `path` of a destroyed window whose widget command is not deleted by Tk, e.g. the
+    // command of a megawidget. The command is moved out of the way, so that a new window can
+    // take the path at once, and is deleted when idle, for it may be running.
+    pub(crate) fn release_widget_command( &self, path: &str ) {
+        self.run(( "trace", "remove", "command", path, "delete", DESTROY_TRACE )).ok();
+        self.release_path( path );
+        let serial = RETIRED_COMMANDS.with( |serial| serial.replace( serial.get() + 1 ));
+        let retired = format!( "::tk_rs_retired_{serial}" );
+        if self.run(( "rename", path, retired.as_str() )).is_ok() {
+            self.delete_commands_later( Some( retired ));
+        }
+    }
+
+    // Deletes the commands when idle, for one of them may be running, e.g. the command of a
+    // button destroying its window.
+    fn delete_commands_later( &self, names: impl IntoIterator<Item=String> ) {
+        for name in names {
+            self.run(( "after", "idle", "catch", Obj::from(( "rename", name, "" )))).ok();
+        }
+    }
+}
+
+impl<Inst:TkInstance> Widget<Inst> {
+    pub(crate) fn from_tracked_path( tk: &Tk<Inst>, path: &str ) -> Self {
+        Widget{ path: tk.track_path( path ), inst: tk.inst, mark: NOT_SEND_SYNC }
+    }
+
+    /// Checks if the window of this handle still exists. It returns `false` once the window
+    /// is destroyed, even if another window has been created at the same path since then.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let root = tk.root();
+    ///
+    ///     let row = root.add_label( "row" -text("first") )?;
+    ///     assert!( row.is_alive() );
+    ///
+    ///     tk.destroy(( row, ))?;
+    ///     assert!( !row.is_alive() );
+    ///
+    ///     let new_row = root.add_label( "row" -text("second") )?;
+    ///     assert!( new_row.is_alive() );
+    ///     assert!( !row.is_alive() );
+    ///     assert!( row.configure( -text("stale") ).is_err() );
+    ///     assert!( row.pack(()).is_err() );
+    ///     assert_eq!( new_row.cget( text )?, "second" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn is_alive( &self ) -> bool {
+        self.path.is_alive()
+    }
+
+    /// Makes this widget own the command created by `tclosure!()`, which is deleted, freeing the
+    /// closure, when the widget is destroyed, or when it is replaced by another value of the
+    /// option of this widget it has been configured as, e.g. `-command`. Returns `script` for use
+    /// as such an option.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let root = tk.root();
+    ///
+    ///     let button = root.add_button( "button" -text("Close") )?;
+    ///     let noop = button.command( tclosure!( tk, || -> TkResult<()> { Ok(()) }))?;
+    ///     button.configure( -command( noop.as_str() ))?;
+    ///
+    ///     let cmd = button.command( tclosure!( tk, move || -> TkResult<()> {
+    ///         Ok( tk.destroy(( button, ))? )
+    ///     }))?;
+    ///     button.configure( -command( cmd.as_str() ))?;
+    ///     tk.run( "update" )?;
+    ///     assert!( tk.eval(( "info", "commands", noop.as_str() ))?.to_string().is_empty() );
+    ///
+    ///     button.invoke()?;
+    ///     tk.run( "update" )?;
+    ///     assert!( tk.eval(( "info", "commands", cmd.as_str() ))?.to_string().is_empty() );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn command( &self, script: String ) -> InterpResult<String> {
+        let name = command_name( &script );
+        if self.path.with_owned( |owned| owned.commands.push( name.clone() )).is_none() {
+            self.tk().delete_commands_later( Some( name ));
+            return Err( WidgetDestroyed( self.path.last_known() ).into() );
+        }
+        Ok( script )
+    }
+
+    // Records the command owned under `key`, e.g. the event sequence it is bound to, deleting
+    // the one previously owned under the same key. The command is deleted at once if the widget
+    // has been destroyed.
+    pub(crate) fn own_keyed_command( &self, key: String, script: &str ) {
+        let name = command_name( script );
+        let replaced = self.path.with_owned( |owned| {
+            let replaced = owned.keyed.insert( key, name.clone() );
+            replaced.filter( |replaced| *replaced != name && !owned.refers_to( replaced ))
+        });
+        match replaced {
+            Some( replaced ) => self.tk().delete_commands_later( replaced ),
+            None => self.tk().delete_commands_later( Some( name )),
+        }
+    }
+
+    // Records `tag` as owned, to remove its bindings when the widget is destroyed, returning
+    // `true` if it was not owned yet. They are removed at once if the widget has been destroyed.
+    pub(crate) fn own_bindtag( &self, tag: &str ) -> bool {
+        let added = self.path.with_owned( |owned| {
+            let added = !owned.bindtags.iter().any( |owned| owned == tag );
+            if added {
+                owned.bindtags.push( tag.to_owned() );
+            }
+            added
+        });
+        added.unwrap_or_else( || {
+            self.tk().unbind_tag( tag );
+            false
+        })
+    }
+
+    // Called after `opt_pairs` have been configured. An owned command set as e.g. "-command"
+    // is owned under the option from now on, so that the command it replaces is deleted.
+    pub(crate) fn own_option_commands( &self, opt_pairs: &[Obj] ) {
+        for pair in opt_pairs.chunks_exact( 2 ) {
+            let option = pair[0].to_string();
+            if !option.ends_with( "command" ) {
+                continue;
+            }
+            let name = command_name( &pair[1].to_string() );
+            let replaced = self.path.with_owned( |owned| {
+                if owned.keyed.get( &option ) == Some( &name ) {
+                    return None;
+                }
+                let replaced = match owned.commands.iter().position( |command| *command == name ) {
+                    Some( index ) => {
+                        let name = owned.commands.remove( index );
+                        owned.keyed.insert( option, name )
+                    },
+                    None => owned.keyed.remove( &option ),
+                };
+                replaced.filter( |replaced| !owned.refers_to( replaced ))
+            });
+            self.tk().delete_commands_later( replaced.flatten() );
+        }
+    }
+
+    /// Throws `WidgetDestroyed` if the window of this handle has been destroyed.
+    pub fn check_alive( &self ) -> Result<(), WidgetDestroyed> {
+        if self.is_alive() {
+            Ok(())
+        } else {
+            Err( WidgetDestroyed( self.path.last_known() ))
+        }
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
     opt::{
         OptPair,
         TkListboxItemOpt,
+        option_value,
     },
     range::{
         TkDefaultStart,
@@ -277,12 +278,14 @@ impl<Inst:TkInstance> TkListbox<Inst> {
         self.0.tk().run( command )
     }
 
-    pub fn itemcget<Opt,Val>( &self, index: impl Into<Index>, _name_fn: fn(Val)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn itemcget<Opt,Val>( &self, index: impl Into<Index>, _name_fn: fn(Val)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkListboxItemOpt>
             , Val : Into<Obj>
     {
-        self.0.tk().eval(( self.0.path, "itemcget", index.into(), <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "itemcget", index.into(), <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn itemconfigure<Opts>( &self, index: impl Into<Index>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -0,0 +1,535 @@
+//! Megawidgets: user-defined widgets composed of Tk widgets.
+//!
+//! A megawidget is a `ttk::frame`, called the hull, containing child widgets, called parts. It has
+//! a widget command visible to Tcl, which handles "configure" and "cget" for its own options and
+//! passes other options and subcommands to the hull. An own option is either stored, notifying
+//! `Megawidget::option_changed()`, or delegated to an option of a part.
+//!
+//! Once registered by `Tk::register_megawidget()`, a megawidget can be created by Tcl scripts, by
+//! `add_megawidget()`, or by `megawidget()` in `add_widgets()`, and queried from
+//! `CreatedWidgets` as `Mega<W,Inst>`, which works with geometry managers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::*;
+//! use tk::*;
+//! use tk::cmd::*;
+//! use tk::megawidget::*;
+//!
+//! struct SearchBox;
+//!
+//! impl Megawidget for SearchBox {
+//!     const COMMAND: &'static str = "searchbox";
+//!     const CLASS: &'static str = "SearchBox";
+//!     const OPTIONS: &'static [MegaOption] = &[
+//!         MegaOption{ name: "-text",        default: "Search", delegate: Some(( "button", "-text"  ))},
+//!         MegaOption{ name: "-width",       default: "20",     delegate: Some(( "entry",  "-width" ))},
+//!         MegaOption{ name: "-placeholder", default: "",       delegate: None },
+//!     ];
+//!
+//!     fn build<Inst:TkInstance>( hull: &Megahull<Inst> ) -> TkResult<()> {
+//!         let entry = hull.add_ttk_entry( "entry" )?.pack( -side("left") -fill("x") -expand(true) )?;
+//!         let button = hull.add_ttk_button( "button" )?.pack( -side("left") )?;
+//!         hull.add_part( "entry", &entry );
+//!         hull.add_part( "button", &button );
+//!         Ok(())
+//!     }
+//!
+//!     fn option_changed<Inst:TkInstance>( hull: &Megahull<Inst>, name: &str, value: &Obj ) -> TkResult<()> {
+//!         if name == "-placeholder" {
+//!             let entry = hull.part( "entry" ).unwrap();
+//!             hull.tk().run(( entry, "delete", 0, "end" ))?;
+//!             hull.tk().run(( entry, "insert", 0, value.clone() ))?;
+//!         }
+//!         Ok(())
+//!     }
+//!
+//!     fn invoke<Inst:TkInstance>( hull: &Megahull<Inst>, subcommand: &str, _args: &[Obj] ) -> TkResult<Option<Obj>> {
+//!         match subcommand {
+//!             "get" => Ok( Some( hull.tk().eval(( hull.part( "entry" ).unwrap(), "get" ))? )),
+//!             _ => Ok( None ),
+//!         }
+//!     }
+//! }
+//!
+//! fn main() -> TkResult<()> {
+//!     let tk = make_tk!()?;
+//!     let root = tk.root();
+//!
+//!     let search = root.add_megawidget::<SearchBox,_,_>( "search" -text("Find") -mega_opt( "-placeholder", "keyword" ))?
+//!         .pack( -fill("x") )?;
+//!     assert_eq!( search.cget( text )?, "Find" );
+//!     assert_eq!( search.call( "get", None )?.to_string(), "keyword" );
+//!
+//!     search.configure( -width(30) )?;
+//!     assert_eq!( tk.eval(( search.part( "entry" ).unwrap(), "cget", "-width" ))?.to_string(), "30" );
+//!
+//!     // options of the hull
+//!     search.configure( -padding(4) )?;
+//!
+//!     // Tcl scripts can create and configure it as well
+//!     tk.run( "searchbox .tcl_search -text Go; .tcl_search configure -placeholder rust" )?;
+//!
+//!     let created = root.add_widgets( -pack( -megawidget::<SearchBox,_,_>( "search2" -text("Go") )))?;
+//!     let search2 = created.query::<Mega<SearchBox,_>>( "search2" ).unwrap();
+//!     assert_eq!( search2.cget( text )?, "Go" );
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
+use crate::{
+    InterpResult,
+    OptPair,
+    PathOptsWidgets,
+    Tk,
+    TkInstance,
+    TkOption,
+    TkResult,
+    TypeId,
+    UpcastFrom,
+    Widget,
+    cmd::{TkGridSlave, TkPackSlave, TkPlaceSlave},
+    opt::option_value,
+    query::UpcastableWidget,
+};
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    ops::{Deref, Neg, Sub},
+};
+
+use tcl::{
+    Obj,
+    error::{DeError, InterpError},
+    tclosure,
+};
+
+use tuplex::{IntoHomoTuple, PushBack};
+
+// The name of the command notified of the destruction of megawidgets.
+const DESTROY_HANDLER: &str = "tk_rs_megawidget_destroyed";
+
+/// An option of a megawidget.
+#[derive( Debug )]
+pub struct MegaOption {
+    /// The option name, e.g. "-placeholder".
+    pub name     : &'static str,
+    /// The value given to the megawidget if not specified on creation.
+    pub default  : &'static str,
+    /// The part and its option which the option is forwarded to, e.g. `Some(( "entry", "-width" ))`.
+    pub delegate : Option<(&'static str, &'static str)>,
+}
+
+/// Definition of a megawidget.
+pub trait Megawidget: 'static {
+    /// The Tcl command creating megawidgets of this type, e.g. "searchbox".
+    const COMMAND: &'static str;
+
+    /// The window class, which class bindings and the option database refer to.
+    const CLASS: &'static str;
+
+    /// The options of its own. Options not listed here are those of the hull.
+    const OPTIONS: &'static [MegaOption] = &[];
+
+    /// Creates the parts in `hull`, registering them with `Megahull::add_part()`.
+    fn build<Inst:TkInstance>( hull: &Megahull<Inst> ) -> TkResult<()>;
+
+    /// Called when an own option which is not delegated is set, including on creation.
+    fn option_changed<Inst:TkInstance>( _hull: &Megahull<Inst>, _name: &str, _value: &Obj ) -> TkResult<()> {
+        Ok(())
+    }
+
+    /// Handles the subcommands other than "configure" and "cget". Returns `None` to pass
+    /// `subcommand` to the hull.
+    fn invoke<Inst:TkInstance>( _hull: &Megahull<Inst>, _subcommand: &str, _args: &[Obj] ) -> TkResult<Option<Obj>> {
+        Ok( None )
+    }
+}
+
+#[derive( Default )]
+struct MegaState {
+    options : HashMap<&'static str, Obj>,
+    parts   : HashMap<String, String>,
+}
+
+thread_local! {
+    static MEGA_STATES: RefCell<HashMap<(TypeId, String), MegaState>> = RefCell::new( HashMap::new() );
+
+    static REGISTERED: RefCell<HashSet<(TypeId, &'static str)>> = RefCell::new( HashSet::new() );
+}
+
+// Finds the command of a registered megawidget, for `add_widgets()` to tell it is a widget.
+pub(crate) fn find_megawidget_command( name: &str ) -> Option<&'static str> {
+    REGISTERED.with( |registered| registered.borrow().iter()
+        .map( |&( _, command )| command )
+        .find( |&command| command == name ))
+}
+
+fn hull_command( path: &str ) -> String {
+    format!( "::tk_rs_hull{path}" )
+}
+
+fn find_option<W:Megawidget>( name: &str ) -> Option<&'static MegaOption> {
+    W::OPTIONS.iter().find( |option| option.name == name )
+}
+
+// The error of a command rejected by a megawidget, as thrown by Tcl's `error`.
+fn tcl_error<T>( message: String ) -> TkResult<T> {
+    Err( InterpError {
+        obj     : Obj::from( message ),
+        options : Obj::from(( "-code", "1", "-errorcode", "NONE" )),
+    }.into() )
+}
+
+/// The hull of a megawidget, given to `Megawidget` to build and update its parts.
+pub struct Megahull<Inst:TkInstance> {
+    widget : Widget<Inst>,
+}
+
+impl<Inst:TkInstance> Deref for Megahull<Inst> {
+    type Target = Widget<Inst>;
+
+    fn deref( &self ) -> &Self::Target { &self.widget }
+}
+
+impl<Inst:TkInstance> Megahull<Inst> {
+    fn key( &self ) -> ( TypeId, String ) {
+        ( TypeId::of::<Inst>(), self.widget.path.to_string() )
+    }
+
+    /// Returns the Tk instance the megawidget belongs to.
+    pub fn tk( &self ) -> Tk<Inst> { self.widget.tk() }
+
+    /// Registers `part` under `name`, which options can be delegated to.
+    pub fn add_part( &self, name: &str, part: &Widget<Inst> ) {
+        MEGA_STATES.with( |states| states.borrow_mut()
+            .entry( self.key() )
+            .or_default()
+            .parts
+            .insert( name.to_owned(), part.path.to_string() ));
+    }
+
+    /// Returns the part registered under `name`.
+    pub fn part( &self, name: &str ) -> Option<Widget<Inst>> {
+        let path = MEGA_STATES.with( |states| states.borrow()
+            .get( &self.key() )
+            .and_then( |state| state.parts.get( name ).cloned() ))?;
+        Some( Widget::from_tracked_path( &self.tk(), &path ))
+    }
+
+    /// Returns the current value of an own option.
+    pub fn option( &self, name: &str ) -> Option<Obj> {
+        MEGA_STATES.with( |states| states.borrow()
+            .get( &self.key() )
+            .and_then( |state| state.options.get( name ).cloned() ))
+    }
+
+    fn set_option<W:Megawidget>( &self, option: &'static MegaOption, value: Obj ) -> TkResult<()> {
+        match option.delegate {
+            Some(( part_name, part_option )) => match self.part( part_name ) {
+                Some( part ) => self.tk().run(( part, "configure", part_option, value ))?,
+                None => tcl_error( format!( "no part \"{part_name}\" for option \"{}\"", option.name ))?,
+            },
+            None => {
+                MEGA_STATES.with( |states| states.borrow_mut()
+                    .entry( self.key() )
+                    .or_default()
+                    .options
+                    .insert( option.name, value.clone() ));
+                W::option_changed( self, option.name, &value )?;
+            },
+        }
+        Ok(())
+    }
+
+    fn get_option( &self, option: &'static MegaOption ) -> TkResult<Obj> {
+        match option.delegate {
+            Some(( part_name, part_option )) => match self.part( part_name ) {
+                Some( part ) => Ok( self.tk().eval(( part, "cget", part_option ))? ),
+                None => tcl_error( format!( "no part \"{part_name}\" for option \"{}\"", option.name )),
+            },
+            None => Ok( self.option( option.name ).unwrap_or_else( || option.default.into() )),
+        }
+    }
+
+    fn option_spec( &self, option: &'static MegaOption ) -> TkResult<Obj> {
+        Ok( Obj::from( vec![
+            Obj::from( option.name ), Obj::from( "" ), Obj::from( "" ),
+            Obj::from( option.default ), self.get_option( option )?,
+        ]))
+    }
+
+    // Handles the widget command of the megawidget.
+    fn dispatch<W:Megawidget>( &self, subcommand: &str, args: Vec<Obj> ) -> TkResult<Obj> {
+        let tk = self.tk();
+        let hull = hull_command( &self.widget.path.to_string() );
+
+        match subcommand {
+            "configure" => match args.len() {
+                0 => {
+                    let mut specs = tk.eval(( hull.as_str(), "configure" ))?.get_elements()?.collect::<Vec<_>>();
+                    for option in W::OPTIONS {
+                        specs.push( self.option_spec( option )? );
+                    }
+                    Ok( Obj::from( specs ))
+                },
+                1 => match find_option::<W>( &args[0].to_string() ) {
+                    Some( option ) => self.option_spec( option ),
+                    None => Ok( tk.eval(( hull.as_str(), "configure", args[0].clone() ))? ),
+                },
+                _ => {
+                    if args.len() % 2 == 1 {
+                        tcl_error( format!( "value for \"{}\" missing", args[ args.len()-1 ]))?;
+                    }
+                    let mut hull_opts = vec![ Obj::from( hull.as_str() ), Obj::from( "configure" )];
+                    for pair in args.chunks( 2 ) {
+                        match find_option::<W>( &pair[0].to_string() ) {
+                            Some( option ) => self.set_option::<W>( option, pair[1].clone() )?,
+                            None => hull_opts.extend( pair.iter().cloned() ),
+                        }
+                    }
+                    if hull_opts.len() > 2 {
+                        tk.run( hull_opts )?;
+                    }
+                    Ok( Obj::new() )
+                },
+            },
+            "cget" => {
+                if args.len() != 1 {
+                    tcl_error( format!( "wrong # args: should be \"{} cget option\"", self.widget.path ))?;
+                }
+                match find_option::<W>( &args[0].to_string() ) {
+                    Some( option ) => self.get_option( option ),
+                    None => Ok( tk.eval(( hull.as_str(), "cget", args[0].clone() ))? ),
+                }
+            },
+            _ => match W::invoke( self, subcommand, &args )? {
+                Some( result ) => Ok( result ),
+                None => {
+                    let mut command = vec![ Obj::from( hull.as_str() ), Obj::from( subcommand )];
+                    command.extend( args );
+                    Ok( tk.eval( command )? )
+                },
+            },
+        }
+    }
+}
+
+impl<Inst:TkInstance> Tk<Inst> {
+    /// Defines the Tcl command `W::COMMAND` creating megawidgets of type `W`. Registering a
+    /// type again does nothing.
+    pub fn register_megawidget<W:Megawidget>( &self ) -> InterpResult<()> {
+        let newly_registered = REGISTERED.with( |registered| registered.borrow_mut().insert(( TypeId::of::<Inst>(), W::COMMAND )));
+        if !newly_registered {
+            return Ok(());
+        }
+
+        if self.eval(( "info", "commands", DESTROY_HANDLER ))?.is_empty() {
+            let tk = *self;
+            let _ = tclosure!( tk, cmd: DESTROY_HANDLER, move |path: String| -> TkResult<()> {
+                MEGA_STATES.with( |states| states.borrow_mut().remove( &( TypeId::of::<Inst>(), path.clone() )));
+                tk.release_widget_command( &path );
+                Ok(())
+            });
+        }
+        self.run(( "bind", W::CLASS, "<Destroy>", format!( "{DESTROY_HANDLER} %W" )))?;
+
+        let tk = *self;
+        let _ = tclosure!( tk, cmd: W::COMMAND, move |path: String, ..| -> TkResult<String> {
+            tk.create_megawidget::<W>( &path, tcl_va_args!() )?;
+            Ok( path )
+        });
+        Ok(())
+    }
+
+    fn create_megawidget<W:Megawidget>( &self, path: &str, args: Vec<Obj> ) -> TkResult<()> {
+        if args.len() % 2 == 1 {
+            tcl_error( format!( "value for \"{}\" missing", args[ args.len()-1 ]))?;
+        }
+
+        let mut own_opts = Vec::<(&'static MegaOption, Obj)>::new();
+        let mut hull_cmd = vec![
+            Obj::from( "ttk::frame" ), Obj::from( path ),
+            Obj::from( "-class" ), Obj::from( W::CLASS ),
+            Obj::from( "-style" ), Obj::from( "TFrame" ),
+        ];
+        for pair in args.chunks( 2 ) {
+            match find_option::<W>( &pair[0].to_string() ) {
+                Some( option ) => own_opts.push(( option, pair[1].clone() )),
+                None => hull_cmd.extend( pair.iter().cloned() ),
+            }
+        }
+
+        self.run( hull_cmd )?;
+        self.run(( "rename", path, hull_command( path )))?;
+
+        let tk = *self;
+        let command_path = path.to_owned();
+        let _ = tclosure!( tk, cmd: path, move |subcommand: String, ..| -> TkResult<Obj> {
+            let hull = Megahull{ widget: Widget::from_tracked_path( &tk, &command_path )};
+            hull.dispatch::<W>( &subcommand, tcl_va_args!() )
+        });
+
+        let hull = Megahull{ widget: Widget::from_tracked_path( self, path )};
+        let built = W::build( &hull ).and_then( |_| {
+            for option in W::OPTIONS {
+                match own_opts.iter().find( |( own, _ )| own.name == option.name ) {
+                    Some(( _, value )) => hull.set_option::<W>( option, value.clone() )?,
+                    None => if option.delegate.is_none() || !option.default.is_empty() {
+                        hull.set_option::<W>( option, option.default.into() )?;
+                    },
+                }
+            }
+            Ok(())
+        });
+
+        if built.is_err() {
+            self.run(( "destroy", path )).ok();
+        }
+        built
+    }
+}
+
+/// A handle of a megawidget of type `W`.
+pub struct Mega<W,Inst:TkInstance>( pub(crate) Widget<Inst>, PhantomData<fn() -> W> );
+
+impl<W,Inst:TkInstance> Clone for Mega<W,Inst> {
+    fn clone( &self ) -> Self { *self }
+}
+
+impl<W,Inst:TkInstance> Copy for Mega<W,Inst> {}
+
+impl<W,Inst:TkInstance> Deref for Mega<W,Inst> {
+    type Target = Widget<Inst>;
+
+    fn deref( &self ) -> &Self::Target { &self.0 }
+}
+
+impl<W:Megawidget,Inst:TkInstance> UpcastFrom<Inst> for Mega<W,Inst> {
+    fn upcast_from( upcastable_widget: UpcastableWidget<Inst> ) -> Option<Self> {
+        if upcastable_widget.name == W::COMMAND {
+            Some( Mega( upcastable_widget.widget, PhantomData ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<W,Inst:TkInstance> From<Mega<W,Inst>> for Obj {
+    fn from( widget: Mega<W,Inst> ) -> Obj { Obj::from( widget.0 )}
+}
+
+impl<W,Inst:TkInstance> TkPackSlave  for Mega<W,Inst> {}
+impl<W,Inst:TkInstance> TkGridSlave  for Mega<W,Inst> {}
+impl<W,Inst:TkInstance> TkPlaceSlave for Mega<W,Inst> {}
+
+impl<W:Megawidget,Inst:TkInstance> Mega<W,Inst> {
+    /// Sets options, either own options of the megawidget or options of its hull.
+    pub fn configure<Opts>( &self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
+        where Opts: IntoHomoTuple<OptPair>
+    {
+        self.0.do_configure( opts.into() )
+    }
+
+    /// Queries the value of an option, converted to the option's value type.
+    #[cex]
+    pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
+        where Opt: TkOption
+    {
+        let obj = self.0.cget( <Opt as TkOption>::NAME )?;
+        ret!( option_value::<Opt>( obj ))
+    }
+
+    /// Queries the value of an option by its name, e.g. "-placeholder".
+    pub fn cget_named( &self, name: &str ) -> InterpResult<Obj> {
+        self.0.check_alive()?;
+        self.0.tk().eval(( self.0.path, "cget", name ))
+    }
+
+    /// Returns the part registered under `name`.
+    pub fn part( &self, name: &str ) -> Option<Widget<Inst>> {
+        Megahull{ widget: self.0 }.part( name )
+    }
+
+    /// Invokes `subcommand` of the widget command with `args`.
+    pub fn call( &self, subcommand: &str, args: impl IntoIterator<Item=Obj> ) -> InterpResult<Obj> {
+        self.0.check_alive()?;
+        let mut command = vec![ Obj::from( self.0.path ), Obj::from( subcommand )];
+        command.extend( args );
+        self.0.tk().eval( command )
+    }
+}
+
+/// Adds a megawidget as a child.
+pub trait AddMegawidget {
+    /// Adds a megawidget of type `W`, registering the type if not yet.
+    fn add_megawidget<W,Opts,Inst:TkInstance>( &self, path_opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Mega<W,Inst>>
+        where Self : Deref<Target=Widget<Inst>>
+            , W    : Megawidget
+            , Opts : IntoHomoTuple<OptPair>
+    {
+        self.deref().tk().register_megawidget::<W>()?;
+        self.deref().add( W::COMMAND, path_opts.into() ).map( |widget| Mega( widget, PhantomData ))
+    }
+}
+
+impl<Widg, Inst:TkInstance> AddMegawidget for Widg where Widg: Deref<Target=Widget<Inst>> {}
+
+/// An option not known to Tk, e.g. an own option of a megawidget.
+#[derive( Debug )]
+pub struct MegaOpt( OptPair );
+
+/// Makes an option named `name`, e.g. "-placeholder", of `value`.
+pub fn mega_opt( name: &'static str, value: impl Into<Obj> ) -> MegaOpt {
+    MegaOpt( OptPair{ name, value: value.into() })
+}
+
+impl From<MegaOpt> for OptPair {
+    fn from( opt: MegaOpt ) -> Self { opt.0 }
+}
+
+impl Neg for MegaOpt {
+    type Output = PathOptsWidgets<(MegaOpt,), ()>;
+
+    fn neg( self ) -> Self::Output {
+        PathOptsWidgets {
+            path   : "",
+            opts   : (self,),
+            widgets: (),
+        }
+    }
+}
+
+impl Sub<MegaOpt> for &'static str {
+    type Output = PathOptsWidgets<(MegaOpt,), ()>;
+
+    fn sub( self, rhs: MegaOpt ) -> Self::Output {
+        PathOptsWidgets {
+            path   : self,
+            opts   : (rhs,),
+            widgets: (),
+        }
+    }
+}
+
+impl<O,L> Sub<MegaOpt> for PathOptsWidgets<O,L>
+    where O: PushBack<MegaOpt>
+{
+    type Output = PathOptsWidgets<<O as PushBack<MegaOpt>>::Output,L>;
+
+    fn sub( self, rhs: MegaOpt ) -> Self::Output {
+        PathOptsWidgets {
+            path   : self.path,
+            opts   : self.opts.push_back( rhs ),
+            widgets: self.widgets,
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
 use crate::{
     InterpResult,
-    NOT_SEND_SYNC,
     PathOptsWidgets,
-    Tk,
     TkInstance,
     TkOption,
     UpcastableWidget,
@@ -12,6 +14,7 @@ use crate::{
         TkMenuOpt,
         TkMenuEntryOpt,
         OptPair,
+        option_value,
     },
     range::{
         TkDefaultEnd,
@@ -36,7 +39,13 @@ use std::{
     },
 };
 
-use tcl::Obj;
+use tcl::{
+    Obj,
+    error::{
+        DeError,
+        InterpError,
+    },
+};
 
 use tuplex::*;
 
@@ -218,18 +227,16 @@ impl<Inst:TkInstance> self::TkMenu<Inst> {
 
     pub fn clone( &self, new_path_name: String, menu_clone_type: TkMenuCloneType ) -> InterpResult<Self> {
         self.0.tk().run(( self.0.path, "clone", new_path_name.as_str(), menu_clone_type ))?;
-        Ok( self::TkMenu( Widget{
-            path : Tk::<Inst>::make_or_get_path( &new_path_name ),
-            inst : self.0.inst,
-            mark : NOT_SEND_SYNC,
-        }))
+        Ok( self::TkMenu( Widget::from_tracked_path( &self.0.tk(), &new_path_name )))
     }
 
-    pub fn entrycget<Opt>( &self, index: impl Into<Index>, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn entrycget<Opt>( &self, index: impl Into<Index>, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkMenuEntryOpt>
     {
-        self.0.tk().eval(( self.0.path, "entrycget", index.into(), <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "entrycget", index.into(), <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn entryconfigure<Opts>( &self, index: impl Into<Index>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -459,12 +466,12 @@ pub trait AddMenus<Inst>
         let opts = path_opts_widgets.opts;
         let widgets = path_opts_widgets.widgets.convert_tuple();
 
-        let mut created_widgets = CreatedWidgets::new( self.deref().path );
+        let mut created_widgets = CreatedWidgets::new( &self.deref().path.to_string() );
 
         let top_menu = self.deref().add( "menu", PathOptsWidgets{ path, opts, widgets: () }).map( |w| TkMenu( w ))?;
         self.deref().tk().run(( self.deref().path, "configure", "-menu", top_menu.0.path ))?;
 
-        let mut current_path = top_menu.0.path.to_owned();
+        let mut current_path = top_menu.0.path.to_string();
         let mut menu_cmd = Vec::<Obj>::new();
         let mut add_cmd = Vec::<Obj>::new();
         let mut is_cascade = false;
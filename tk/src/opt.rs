@@ -1,6 +1,8 @@
 use crate::*;
 use crate::cmd::*;
 use std::ops::{Neg, Sub};
+use std::os::raw::c_double;
+use tcl::{from_obj, error::{DeError, DeKind}};
 use tuplex::*;
 
 #[derive( Debug )]
@@ -17,15 +19,76 @@ impl OptPair {
 
 pub trait TkOption {
     const NAME: &'static str;
+
+    /// The type of the option's value, as returned by `cget()` and its kin.
+    type Value: TkOptionValue;
+}
+
+/// Conversion of an option's value returned by Tk into Rust type.
+pub trait TkOptionValue: Sized {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError>;
+}
+
+impl TkOptionValue for Obj {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> { Ok( obj )}
+}
+
+impl TkOptionValue for String {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> { Ok( obj.to_string() )}
+}
+
+impl TkOptionValue for bool {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> { bool::try_from( obj )}
+}
+
+impl TkOptionValue for c_double {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> { c_double::try_from( obj )}
+}
+
+/// Accepts integral real numbers such as "1.0" too, as canvas items report their widths.
+impl TkOptionValue for c_int {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> {
+        c_int::try_from( obj.clone() ).or_else( |err| match c_double::try_from( obj.clone() ) {
+            Ok( value ) if value.fract() == 0.0 && value.abs() <= c_int::MAX as c_double => Ok( value as c_int ),
+            Ok( _ ) => Err( DeError::new( DeKind::NotI32, obj )),
+            Err( _ ) => Err( err ),
+        })
+    }
+}
+
+impl TkOptionValue for TkWidgetState {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> { from_obj::<TkWidgetState>( obj )}
+}
+
+/// An empty value is `None`, e.g. the "-state" of canvas items following the canvas.
+impl<T:TkOptionValue> TkOptionValue for Option<T> {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> {
+        if obj.is_empty() {
+            Ok( None )
+        } else {
+            T::from_option_value( obj ).map( Some )
+        }
+    }
+}
+
+// Converts the value of `Opt` returned by Tk.
+pub(crate) fn option_value<Opt:TkOption>( obj: Obj ) -> Result<Opt::Value, DeError> {
+    <Opt::Value as TkOptionValue>::from_option_value( obj )
+}
+
+macro_rules! opt_value {
+    () => { Obj };
+    ($value:ty) => { $value };
 }
 
 macro_rules! def_opts {
-    ($($ty:ident $trait:ident $str:expr;)+) => {$(
+    ($($ty:ident $trait:ident $str:expr $(=> $value:ty)?;)+) => {$(
         #[derive( Debug )]
         pub struct $ty( Obj );
 
         impl crate::TkOption for $ty {
             const NAME: &'static str = $str;
+            type Value = opt_value!( $($value)? );
         }
 
         impl<T:Into<Obj>> $trait for T {
@@ -80,6 +143,8 @@ macro_rules! def_opts {
     )+};
 }
 
+// The option structs share their names with the enums of option values at the crate root, e.g.
+// `types::TkFill`, which are therefore referred to as `types::` here.
 def_opts! {
     TkAbove                     TkAboveFn                       "-above"                        ;
     TkAccelerator               TkAcceleratorFn                 "-accelerator"                  ;
@@ -99,7 +164,7 @@ def_opts! {
     TkAfter                     TkAfterFn                       "-after"                        ;
     TkAlign                     TkAlignFn                       "-align"                        ;
     TkAlpha                     TkAlphaFn                       "-alpha"                        ;
-    TkAnchor                    TkAnchorFn                      "-anchor"                       ;
+    TkAnchor                    TkAnchorFn                      "-anchor"                       => Option<types::TkAnchor>;
     TkAngle                     TkAngleFn                       "-angle"                        ;
     TkArrow                     TkArrowFn                       "-arrow"                        ;
     TkArrowShape                TkArrowShapeFn                  "-arrowshape"                   ;
@@ -133,7 +198,7 @@ def_opts! {
     TkColumns                   TkColumnsFn                     "-columns"                      ;
     TkCommand                   TkCommandFn                     "-command"                      ;
     TkCompositingRule           TkCompositingruleFn             "-compositingrule"              ;
-    TkCompound                  TkCompoundFn                    "-compound"                     ;
+    TkCompound                  TkCompoundFn                    "-compound"                     => Option<types::TkCompound>;
     TkConfine                   TkConfineFn                     "-confine"                      ;
     TkConfirmOverwrite          TkConfirmOverwriteFn            "-confirmoverwrite"             ;
     TkContainer                 TkContainerFn                   "-container"                    ;
@@ -166,7 +231,7 @@ def_opts! {
     TkElide                     TkElideFn                       "-elide"                        ;
     TkEndline                   TkEndlineFn                     "-endline"                      ;
     TkExpand                    TkExpandFn                      "-expand"                       ;
-    TkExportSelection           TkExportSelectionFn             "-exportselection"              ;
+    TkExportSelection           TkExportSelectionFn             "-exportselection"              => bool;
     TkExtent                    TkExtentFn                      "-extent"                       ;
     TkFamily                    TkFamilyFn                      "-family"                       ;
     TkFgStripple                TkFgStrippleFn                  "-fgstripple"                   ;
@@ -179,13 +244,13 @@ def_opts! {
     TkFontMap                   TkFontMapFn                     "-fontmap"                      ;
     TkForeground                TkForegroundFn                  "-foreground"                   ;
     TkFormat                    TkFormatFn                      "-format"                       ;
-    TkFrom                      TkFromFn                        "-from"                         ;
+    TkFrom                      TkFromFn                        "-from"                         => c_double;
     TkFullScreen                TkFullScreenFn                  "-fullscreen"                   ;
     TkGamma                     TkGammaFn                       "-gamma"                        ;
     TkGrayscale                 TkGrayscaleFn                   "-grayscale"                    ;
     TkHandlePad                 TkHandlePadFn                   "-handlepad"                    ;
     TkHandleSize                TkHandleSizeFn                  "-handlesize"                   ;
-    TkHeight                    TkHeightFn                      "-height"                       ;
+    TkHeight                    TkHeightFn                      "-height"                       => Option<types::TkDistance>;
     TkHide                      TkHideFn                        "-hide"                         ;
     TkHideMargin                TkHideMarginFn                  "-hidemargin"                   ;
     TkHighlightBackground       TkHighlightBackgroundFn         "-highlightbackground"          ;
@@ -216,10 +281,10 @@ def_opts! {
     TkIPadY                     TkIPadYFn                       "-ipady"                        ;
     TkJoinStyle                 TkJoinStyleFn                   "-joinstyle"                    ;
     TkJump                      TkJumpFn                        "-jump"                         ;
-    TkJustify                   TkJustifyFn                     "-justify"                      ;
+    TkJustify                   TkJustifyFn                     "-justify"                      => Option<types::TkJustify>;
     TkKeyCode                   TkKeyCodeFn                     "-keycode"                      ;
     TkKeySym                    TkKeySymFn                      "-keysym"                       ;
-    TkLabel                     TkLabelFn                       "-label"                        ;
+    TkLabel                     TkLabelFn                       "-label"                        => String;
     TkLabelAnchor               TkLabelAnchorFn                 "-labelanchor"                  ;
     TkLabelWidget               TkLabelWidgetFn                 "-labelwidget"                  ;
     TkLength                    TkLengthFn                      "-length"                       ;
@@ -245,7 +310,7 @@ def_opts! {
     TkOnValue                   TkOnValueFn                     "-onvalue"                      ;
     TkOpaqueResize              TkOpaqueResizeFn                "-opaqueresize"                 ;
     TkOpen                      TkOpenFn                        "-open"                         ;
-    TkOrient                    TkOrientFn                      "-orient"                       ;
+    TkOrient                    TkOrientFn                      "-orient"                       => types::TkOrient;
     TkOutline                   TkOutlineFn                     "-outline"                      ;
     TkOutlineOffset             TkOutlineOffsetFn               "-outlineoffset"                ;
     TkOutlineStipple            TkOutlineStippleFn              "-outlinestipple"               ;
@@ -273,14 +338,14 @@ def_opts! {
     TkRMargin                   TkRMarginFn                     "-rmargin"                      ;
     TkRMarginColor              TkRMarginColorFn                "-rmargincolor"                 ;
     TkReadOnlyBackground        TkReadOnlyBackgroundFn          "-readonlybackground"           ;
-    TkRelief                    TkReliefFn                      "-relief"                       ;
+    TkRelief                    TkReliefFn                      "-relief"                       => Option<types::TkRelief>;
     TkRelHeight                 TkRelHeightFn                   "-relheight"                    ;
     TkRelWidth                  TkRelWidthFn                    "-relwidth"                     ;
     TkRelX                      TkRelXFn                        "-relx"                         ;
     TkRelY                      TkRelYFn                        "-rely"                         ;
     TkRepeatDelay               TkRepeatDelayFn                 "-repeatdelay"                  ;
     TkRepeatInterval            TkRepeatIntervalFn              "-repeatinterval"               ;
-    TkResolution                TkResolutionFn                  "-resolution"                   ;
+    TkResolution                TkResolutionFn                  "-resolution"                   => c_double;
     TkRoot                      TkRootFn                        "-root"                         ;
     TkRootX                     TkRootxFn                       "-rootx"                        ;
     TkRootY                     TkRootyFn                       "-rooty"                        ;
@@ -301,7 +366,7 @@ def_opts! {
     TkSelectMode                TkSelectModeFn                  "-selectmode"                   ;
     TkSendEvent                 TkSendEventFn                   "-sendevent"                    ;
     TkSerial                    TkSerialFn                      "-serial"                       ;
-    TkSetGrid                   TkSetGridFn                     "-setgrid"                      ;
+    TkSetGrid                   TkSetGridFn                     "-setgrid"                      => bool;
     TkSettings                  TkSettingsFn                    "-settings"                     ;
     TkShow                      TkShowFn                        "-show"                         ;
     TkShowHandle                TkShowHandleFn                  "-showhandle"                   ;
@@ -319,8 +384,8 @@ def_opts! {
     TkSplineSteps               TkSplineStepsFn                 "-splinesteps"                  ;
     TkStart                     TkStartFn                       "-start"                        ;
     TkStartline                 TkStartlineFn                   "-startline"                    ;
-    TkState                     TkStateFn                       "-state"                        ;
-    TkSticky                    TkStickyFn                      "-sticky"                       ;
+    TkState                     TkStateFn                       "-state"                        => Option<TkWidgetState>;
+    TkSticky                    TkStickyFn                      "-sticky"                       => types::TkSticky;
     TkStipple                   TkStippleFn                     "-stipple"                      ;
     TkStretch                   TkStretchFn                     "-stretch"                      ;
     TkStyle                     TkStyleFn                       "-style"                        ;
@@ -332,13 +397,13 @@ def_opts! {
     TkTakeFocus                 TkTakeFocusFn                   "-takefocus"                    ;
     TkTearOff                   TkTearOffFn                     "-tearoff"                      ;
     TkTearOffCommand            TkTearOffCommandFn              "-tearoffcommand"               ;
-    TkText                      TkTextFn                        "-text"                         ;
-    TkTextVariable              TkTextVariableFn                "-textvariable"                 ;
+    TkText                      TkTextFn                        "-text"                         => String;
+    TkTextVariable              TkTextVariableFn                "-textvariable"                 => String;
     TkTickInterval              TkTickIntervalFn                "-tickinterval"                 ;
-    TkTitle                     TkTitleFn                       "-title"                        ;
+    TkTitle                     TkTitleFn                       "-title"                        => String;
     TkTitlePath                 TkTitlePathFn                   "-titlepath"                    ;
     TkTime                      TkTimeFn                        "-time"                         ;
-    TkTo                        TkToFn                          "-to"                           ;
+    TkTo                        TkToFn                          "-to"                           => c_double;
     TkToolWindow                TkToolWindowFn                  "-toolwindow"                   ;
     TkTopmost                   TkTopmostFn                     "-topmost"                      ;
     TkTransparent               TkTransparentFn                 "-transparent"                  ;
@@ -353,7 +418,7 @@ def_opts! {
     TkUndo                      TkUndoFn                        "-undo"                         ;
     TkUniform                   TkUniformFn                     "-uniform"                      ;
     TkUse                       TkUseFn                         "-use"                          ;
-    TkVariable                  TkVariableFn                    "-variable"                     ;
+    TkVariable                  TkVariableFn                    "-variable"                     => String;
     TkValidate                  TkValidateFn                    "-validate"                     ;
     TkValidateCommand           TkValidateCommandFn             "-validatecommand"              ;
     TkValue                     TkValueFn                       "-value"                        ;
@@ -361,7 +426,7 @@ def_opts! {
     TkVisible                   TkVisibleFn                     "-visible"                      ;
     TkVisual                    TkVisualFn                      "-visual"                       ;
     TkWeight                    TkWeightFn                      "-weight"                       ;
-    TkWidth                     TkWidthFn                       "-width"                        ;
+    TkWidth                     TkWidthFn                       "-width"                        => Option<types::TkDistance>;
     TkWindow                    TkWindowFn                      "-window"                       ;
     TkWarp                      TkWarpFn                        "-warp"                         ;
     TkWhen                      TkWhenFn                        "-when"                         ;
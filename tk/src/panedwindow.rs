@@ -17,6 +17,7 @@ use crate::{
         TkPaneConfigureOpt,
         TkPanedwindowOpt,
         OptPair,
+        option_value,
     },
 };
 
@@ -83,12 +84,14 @@ impl<Inst:TkInstance> TkPanedwindow<Inst> {
         })
     }
 
-    pub fn panecget<Opt,Val>( &self, index: c_int, _name_fn: fn(Val)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn panecget<Opt,Val>( &self, index: c_int, _name_fn: fn(Val)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkPaneConfigureOpt>
             , Val : Into<Obj>
     {
-        self.0.tk().eval(( self.0.path, "panecget", index, <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "panecget", index, <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn paneconfigure<Opts>( &self, index: c_int, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -5,6 +5,7 @@ use crate::{
         DeError,
         InterpError,
     },
+    opt::option_value,
     types::TkRGB,
 };
 
@@ -47,11 +48,13 @@ impl<Inst:TkInstance> Photo<Inst> {
         self.tk().run(( self.name.clone(), "blank" ))
     }
 
-    pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn cget<Opt>( &self, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<opt::TkPhotoOpt>
     {
-        self.tk().eval(( self.name.clone(), "cget", <Opt as TkOption>::NAME ))
+        let obj = self.tk().eval(( self.name.clone(), "cget", <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn configure<Opts>( &self, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
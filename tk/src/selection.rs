@@ -0,0 +1,143 @@
+use enumx::export::*;
+use enumx::predefined::*;
+use cex::*;
+
+use crate::{
+    InterpResult,
+    Tk,
+    TkInstance,
+    TkResult,
+    Widget,
+    error::{
+        SelectionEmpty,
+        UnsupportedSelectionType,
+    },
+};
+
+use tcl::{
+    Obj,
+    error::InterpError,
+    tclosure,
+};
+
+impl<Inst:TkInstance> Tk<Inst> {
+    /// Retrieves `selection`, e.g. "PRIMARY" or "CLIPBOARD", as `type_`, e.g. "STRING" or
+    /// "UTF8_STRING".
+    ///
+    /// Throws `SelectionEmpty` if no window owns `selection`, or `UnsupportedSelectionType`
+    /// if the owner does not provide `type_`.
+    #[cex]
+    pub fn selection_get( &self, selection: &str, type_: &str ) -> Result!( String throws InterpError, SelectionEmpty, UnsupportedSelectionType ) {
+        match self.eval(( "selection", "get", "-selection", selection, "-type", type_ )) {
+            Ok( data ) => Ok( data.to_string() ),
+            Err( err ) => {
+                let available = self.selection_targets( selection );
+                if available.is_empty() {
+                    throw!( SelectionEmpty( err ));
+                }
+                if available.iter().any( |target| target == type_ ) {
+                    throw!( err );
+                }
+                throw!( UnsupportedSelectionType{ requested: type_.to_owned(), available });
+            },
+        }
+    }
+
+    /// Retrieves the "PRIMARY" selection as type "STRING".
+    #[cex]
+    pub fn selection_get_text( &self ) -> Result!( String throws InterpError, SelectionEmpty, UnsupportedSelectionType ) {
+        ret!( self.selection_get( "PRIMARY", "STRING" ))
+    }
+
+    /// Clears `selection`, so that no window owns it.
+    pub fn selection_clear( &self, selection: &str ) -> InterpResult<()> {
+        self.run(( "selection", "clear", "-selection", selection ))
+    }
+
+    /// Returns the window in this application owning `selection`, if any.
+    pub fn selection_owner( &self, selection: &str ) -> InterpResult<Option<Widget<Inst>>> {
+        let owner = self.eval(( "selection", "own", "-selection", selection ))?.to_string();
+        if owner.is_empty() {
+            Ok( None )
+        } else {
+            Widget::from_name( &owner, self.inst )
+        }
+    }
+
+    /// Makes `widget` the owner of `selection`. The previous owner, if any, is notified.
+    pub fn selection_own( &self, widget: &Widget<Inst>, selection: &str ) -> InterpResult<()> {
+        self.run(( "selection", "own", "-selection", selection, widget.path ))
+    }
+
+    /// Makes `widget` the owner of `selection`, calling `on_lost` when another window,
+    /// possibly of another application, takes it away. The closure is freed when replaced by
+    /// another call for the same selection, or when `widget` is destroyed.
+    pub fn selection_own_with( &self, widget: &Widget<Inst>, selection: &str, on_lost: impl Fn() + 'static ) -> InterpResult<()> {
+        let command = tclosure!( self, move || -> InterpResult<()> {
+            on_lost();
+            Ok(())
+        });
+        widget.own_keyed_command( format!( "selection own {selection}" ), &command );
+        self.run(( "selection", "own", "-selection", selection, "-command", command, widget.path ))
+    }
+
+    /// Provides the "PRIMARY" selection of type `type_` when `widget` owns it, by calling
+    /// `handler( offset, max_chars )`. The handler returns at most `max_chars` characters of
+    /// the selection, starting at character `offset`. If it returns exactly `max_chars`
+    /// characters, it will be called again for the rest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let canvas = tk.root().add_canvas(())?;
+    ///
+    ///     let selected = "text selected in the canvas";
+    ///     tk.selection_handle( &canvas, "STRING", move |offset, max_chars| {
+    ///         selected.chars().skip( offset ).take( max_chars ).collect()
+    ///     })?;
+    ///     tk.selection_own( &canvas, "PRIMARY" )?;
+    ///     assert_eq!( tk.selection_get_text()?, selected );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn selection_handle( &self, widget: &Widget<Inst>, type_: &str, handler: impl Fn( usize, usize ) -> String + 'static ) -> InterpResult<()> {
+        self.selection_handle_typed( widget, "PRIMARY", type_, "STRING", handler )
+    }
+
+    /// Provides `selection` of type `type_` in `format` when `widget` owns it, by calling
+    /// `handler( offset, max_chars )`. See `selection_handle()`.
+    ///
+    /// The handler is freed when replaced by another one for the same selection and type, or
+    /// when `widget` is destroyed.
+    pub fn selection_handle_typed( &self, widget: &Widget<Inst>, selection: &str, type_: &str, format: &str, handler: impl Fn( usize, usize ) -> String + 'static ) -> InterpResult<()> {
+        let command = tclosure!( self, move |offset: usize, max_chars: usize| -> TkResult<String> {
+            Ok( handler( offset, max_chars ))
+        });
+        widget.own_keyed_command( format!( "selection handle {selection} {type_}" ), &command );
+        self.run(( "selection", "handle", "-selection", selection, "-type", type_, "-format", format, widget.path, command ))
+    }
+
+    // Lists the data types the owner of `selection` provides, or nothing if it is not owned.
+    pub(crate) fn selection_targets( &self, selection: &str ) -> Vec<String> {
+        self.eval(( "selection", "get", "-selection", selection, "-type", "TARGETS" ))
+            .ok()
+            .and_then( |targets| targets.get_elements().ok() )
+            .map( |targets| targets
+                .map( |target| target.to_string() )
+                .filter( |target| !is_meta_target( target ))
+                .collect() )
+            .unwrap_or_default()
+    }
+}
+
+// Targets every selection owner reports, which tell nothing about the data held.
+fn is_meta_target( target: &str ) -> bool {
+    matches!( target, "TARGETS" | "MULTIPLE" | "TIMESTAMP" | "TK_APPLICATION" | "TK_WINDOW" )
+}
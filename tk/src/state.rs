@@ -0,0 +1,486 @@
+//! Reactive state which widgets can be bound to.
+//!
+//! A `Signal` holds a value in Rust. Setting it notifies its subscribers, derived signals and
+//! bound widgets, not at once but in a batch, when the event loop becomes idle. Many updates of
+//! many signals in one event handler thus end up in a single redraw.
+//!
+//! Binding a signal to a widget's `-textvariable` or `-variable` makes the update two-way: the
+//! signal is backed by a Tcl variable, and edits made by the user are written back to the signal
+//! by a variable trace. A bound widget keeps the signal alive until it is destroyed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tcl::*;
+//! use tk::*;
+//! use tk::cmd::*;
+//! use tk::state::Store;
+//!
+//! fn main() -> TkResult<()> {
+//!     let tk = make_tk!()?;
+//!     let root = tk.root();
+//!     let store = Store::new( &tk );
+//!
+//!     let name = store.signal( String::from( "world" ));
+//!     let greeting = name.map( |name| format!( "Hello, {name}!" ));
+//!
+//!     let entry = root.add_ttk_entry(())?;
+//!     entry.bind_value( &name )?;
+//!     let label = root.add_ttk_label(())?;
+//!     label.bind_text( &greeting )?;
+//!     drop( greeting ); // kept alive by the label
+//!     assert_eq!( label.cget( text )?, "Hello, world!" );
+//!
+//!     // what typing in the entry does
+//!     tk.set( name.var_name( &tk )?, "Tk" );
+//!     assert_eq!( name.get(), "Tk" );
+//!
+//!     tk.run( "update idletasks" )?;
+//!     assert_eq!( label.cget( text )?, "Hello, Tk!" );
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    InterpResult,
+    Tk,
+    TkInstance,
+    TkOption,
+    TkResult,
+    Widget,
+    WidgetPath,
+};
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    mem,
+    rc::{Rc, Weak},
+};
+
+use tcl::{
+    Obj,
+    tclosure,
+};
+
+// Flushing stops after this many rounds, in case that signals keep setting each other.
+const MAX_FLUSH_ROUNDS: usize = 100;
+
+// A token of the binding of a widget's option to a signal, held along with the signal by the
+// widget. It is `false` once the option has been bound again.
+type BindingToken = Rc<Cell<bool>>;
+
+// A widget and its option.
+type BindingKey = ( WidgetPath, String );
+
+thread_local! {
+    // The tokens of the current bindings.
+    static BINDINGS: RefCell<HashMap<BindingKey, Weak<Cell<bool>>>> = RefCell::new( HashMap::new() );
+}
+
+/// Owner of signals, which batches their updates and flushes them when idle.
+#[derive( Clone )]
+pub struct Store {
+    inner: Rc<StoreInner>,
+}
+
+struct StoreInner {
+    eval      : Box<dyn Fn( Obj ) -> InterpResult<Obj>>,
+    flush_cmd : String,
+    pending   : RefCell<Vec<Rc<dyn Flush>>>,
+    scheduled : Cell<bool>,
+    serial    : Cell<usize>,
+}
+
+trait Flush {
+    fn flush( &self );
+}
+
+impl Store {
+    /// Creates a store of signals, flushing their updates in the event loop of `tk`.
+    pub fn new<Inst:TkInstance>( tk: &Tk<Inst> ) -> Self {
+        let tk = *tk;
+        let inner = Rc::new_cyclic( |weak: &Weak<StoreInner>| {
+            let weak = weak.clone();
+            let flush_cmd = tclosure!( tk, move || -> TkResult<()> {
+                if let Some( inner ) = weak.upgrade() {
+                    inner.scheduled.set( false );
+                    Store{ inner }.flush();
+                }
+                Ok(())
+            });
+            StoreInner {
+                eval      : Box::new( move |script| tk.eval( script )),
+                flush_cmd : flush_cmd.trim().to_owned(),
+                pending   : RefCell::new( Vec::new() ),
+                scheduled : Cell::new( false ),
+                serial    : Cell::new( 0 ),
+            }
+        });
+        Store{ inner }
+    }
+
+    /// Creates a signal holding `value`.
+    pub fn signal<T:Clone+'static>( &self, value: T ) -> Signal<T> {
+        Signal{ node: Rc::new( Node::new( self.inner.clone(), value ))}
+    }
+
+    /// Delivers pending updates right now, rather than waiting for the event loop to be idle.
+    pub fn flush( &self ) {
+        for _ in 0..MAX_FLUSH_ROUNDS {
+            let batch = mem::take( &mut *self.inner.pending.borrow_mut() );
+            if batch.is_empty() {
+                return;
+            }
+            for node in batch {
+                node.flush();
+            }
+        }
+    }
+}
+
+impl StoreInner {
+    fn schedule( self: &Rc<Self>, node: Rc<dyn Flush> ) {
+        self.pending.borrow_mut().push( node );
+        if !self.scheduled.replace( true )
+            && ( self.eval )( Obj::from(( "after", "idle", self.flush_cmd.as_str() ))).is_err()
+        {
+            self.scheduled.set( false );
+        }
+    }
+
+    // Deletes the command later, since it may be the one being run.
+    fn delete_command_later( &self, name: String ) {
+        ( self.eval )( Obj::from(( "after", "idle", "catch", Obj::from(( "rename", name, "" ))))).ok();
+    }
+
+    fn next_var_name( &self ) -> String {
+        let serial = self.serial.get();
+        self.serial.set( serial + 1 );
+        format!( "::tk_rs_signal_{serial}" )
+    }
+}
+
+impl Drop for StoreInner {
+    fn drop( &mut self ) {
+        let flush_cmd = mem::take( &mut self.flush_cmd );
+        self.delete_command_later( flush_cmd );
+    }
+}
+
+/// A value observed by subscribers, derived signals and widgets. Cloning a signal gives another
+/// handle to the same value.
+pub struct Signal<T> {
+    node: Rc<Node<T>>,
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone( &self ) -> Self { Signal{ node: self.node.clone() }}
+}
+
+// The observers return `false` to unsubscribe.
+type Observer<T> = Box<dyn Fn( &T ) -> bool>;
+
+struct Node<T> {
+    store     : Rc<StoreInner>,
+    value     : RefCell<T>,
+    dirty     : Cell<bool>,
+    var_stale : Cell<bool>,
+    writing   : Cell<bool>,
+    var       : RefCell<Option<BoundVar<T>>>,
+    observers : RefCell<Vec<Observer<T>>>,
+}
+
+// The Tcl variable backing a signal bound to widgets.
+struct BoundVar<T> {
+    name  : String,
+    trace : String,
+    write : Box<dyn Fn( &T ) -> Obj>,
+}
+
+impl<T> Node<T> {
+    fn new( store: Rc<StoreInner>, value: T ) -> Self {
+        Node {
+            store,
+            value     : RefCell::new( value ),
+            dirty     : Cell::new( false ),
+            var_stale : Cell::new( false ),
+            writing   : Cell::new( false ),
+            var       : RefCell::new( None ),
+            observers : RefCell::new( Vec::new() ),
+        }
+    }
+}
+
+impl<T:Clone+'static> Node<T> {
+    fn changed( self: &Rc<Self>, var_stale: bool ) {
+        if var_stale {
+            self.var_stale.set( true );
+        }
+        if !self.dirty.replace( true ) {
+            self.store.schedule( self.clone() );
+        }
+    }
+}
+
+impl<T:Clone+'static> Flush for Node<T> {
+    fn flush( &self ) {
+        if !self.dirty.replace( false ) {
+            return;
+        }
+        let value = self.value.borrow().clone();
+
+        if self.var_stale.replace( false ) {
+            if let Some( var ) = self.var.borrow().as_ref() {
+                self.writing.set( true );
+                ( self.store.eval )( Obj::from(( "set", var.name.as_str(), ( var.write )( &value )))).ok();
+                self.writing.set( false );
+            }
+        }
+
+        // Observers may subscribe to this signal, so they are not borrowed while running.
+        let mut observers = mem::take( &mut *self.observers.borrow_mut() );
+        observers.retain( |observer| observer( &value ));
+        let mut current = self.observers.borrow_mut();
+        observers.append( &mut current );
+        *current = observers;
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop( &mut self ) {
+        if let Some( var ) = self.var.get_mut().take() {
+            ( self.store.eval )( Obj::from(( "unset", "-nocomplain", var.name ))).ok();
+            self.store.delete_command_later( var.trace );
+        }
+    }
+}
+
+impl<T:Clone+'static> Signal<T> {
+    /// Returns a clone of the value.
+    pub fn get( &self ) -> T {
+        self.node.value.borrow().clone()
+    }
+
+    /// Calls `f` with a reference to the value, avoiding a clone.
+    pub fn with<R>( &self, f: impl FnOnce( &T ) -> R ) -> R {
+        f( &self.node.value.borrow() )
+    }
+
+    /// Sets the value. Subscribers, derived signals and bound widgets are notified when idle,
+    /// or when `Store::flush()` is called.
+    pub fn set( &self, value: T ) {
+        *self.node.value.borrow_mut() = value;
+        self.node.changed( true );
+    }
+
+    /// Modifies the value in place, then notifies as `set()` does.
+    pub fn update( &self, f: impl FnOnce( &mut T )) {
+        f( &mut self.node.value.borrow_mut() );
+        self.node.changed( true );
+    }
+
+    /// Calls `f` with the value on each batch of updates.
+    pub fn subscribe( &self, f: impl Fn( &T ) + 'static ) {
+        self.observe( move |value| { f( value ); true });
+    }
+
+    fn observe( &self, f: impl Fn( &T ) -> bool + 'static ) {
+        self.node.observers.borrow_mut().push( Box::new( f ));
+    }
+
+    /// Creates a signal computed from this one by `f`, which is recomputed on updates.
+    pub fn map<U:Clone+'static>( &self, f: impl Fn( &T ) -> U + 'static ) -> Signal<U> {
+        let derived = Signal{ node: Rc::new( Node::new( self.node.store.clone(), self.with( &f )))};
+        let weak = Rc::downgrade( &derived.node );
+        self.observe( move |value| match weak.upgrade() {
+            Some( node ) => { Signal{ node }.set( f( value )); true },
+            None => false,
+        });
+        derived
+    }
+
+    /// Creates a signal computed from this one and `other` by `f`, which is recomputed when
+    /// either of them updates.
+    pub fn combine<U,V>( &self, other: &Signal<U>, f: impl Fn( &T, &U ) -> V + 'static ) -> Signal<V>
+        where U: Clone + 'static
+            , V: Clone + 'static
+    {
+        let f = Rc::new( f );
+        let derived = Signal{ node: Rc::new( Node::new( self.node.store.clone(), self.with( |t| other.with( |u| f( t, u )))))};
+
+        let ( this, that ) = ( Rc::downgrade( &self.node ), Rc::downgrade( &other.node ));
+        let compute = move |derived: &Weak<Node<V>>| -> bool {
+            match ( derived.upgrade(), this.upgrade(), that.upgrade() ) {
+                ( Some( node ), Some( this ), Some( that )) => {
+                    let value = f( &this.value.borrow(), &that.value.borrow() );
+                    Signal{ node }.set( value );
+                    true
+                },
+                _ => false,
+            }
+        };
+        let compute = Rc::new( compute );
+
+        let ( weak, recompute ) = ( Rc::downgrade( &derived.node ), compute.clone() );
+        self.observe( move |_| recompute( &weak ));
+        let weak = Rc::downgrade( &derived.node );
+        other.observe( move |_| compute( &weak ));
+
+        derived
+    }
+}
+
+impl<T> Signal<T>
+    where T: Clone + Into<Obj> + TryFrom<Obj> + 'static
+{
+    /// Returns the name of the Tcl variable backing this signal, creating it on first call.
+    /// Writes to the variable from Tcl, e.g. by editing an entry, update the signal.
+    pub fn var_name<Inst:TkInstance>( &self, tk: &Tk<Inst> ) -> InterpResult<String> {
+        if let Some( var ) = self.node.var.borrow().as_ref() {
+            return Ok( var.name.clone() );
+        }
+
+        let name = self.node.store.next_var_name();
+        tk.run(( "set", name.as_str(), self.get() ))?;
+
+        let weak = Rc::downgrade( &self.node );
+        let tk = *tk;
+        let var = name.clone();
+        let trace = tclosure!( tk, move |_name1: String, _name2: String, _op: String| -> TkResult<()> {
+            if let Some( node ) = weak.upgrade() {
+                if !node.writing.get() {
+                    if let Ok( value ) = T::try_from( tk.get( var.as_str() )? ) {
+                        *node.value.borrow_mut() = value;
+                        node.changed( false );
+                    }
+                }
+            }
+            Ok(())
+        });
+        let trace = trace.trim().to_owned();
+        tk.run(( "trace", "add", "variable", name.as_str(), "write", trace.as_str() ))?;
+
+        *self.node.var.borrow_mut() = Some( BoundVar{ name: name.clone(), trace, write: Box::new( |value| value.clone().into() )});
+        Ok( name )
+    }
+}
+
+// A bound widget keeps its signal alive, by owning a command holding it, until it is destroyed
+// or the option is bound to another signal.
+impl<Inst:TkInstance> Widget<Inst> {
+    /// Shows the value of `signal` as the text of this widget, via `-textvariable`.
+    pub fn bind_text<T>( &self, signal: &Signal<T> ) -> InterpResult<()>
+        where T: Clone + Into<Obj> + TryFrom<Obj> + 'static
+    {
+        self.bind_var( "-textvariable", signal )
+    }
+
+    /// Binds the value of this widget to `signal` both ways: `-textvariable` of entries,
+    /// spinboxes and comboboxes, or `-variable` of scales, radiobuttons and progressbars.
+    pub fn bind_value<T>( &self, signal: &Signal<T> ) -> InterpResult<()>
+        where T: Clone + Into<Obj> + TryFrom<Obj> + 'static
+    {
+        let option = match self.winfo_class()?.as_str() {
+            "Entry" | "TEntry" | "Spinbox" | "TSpinbox" | "TCombobox" => "-textvariable",
+            _ => "-variable",
+        };
+        self.bind_var( option, signal )
+    }
+
+    /// Binds the state of this checkbutton to `signal` both ways.
+    pub fn bind_checked( &self, signal: &Signal<bool> ) -> InterpResult<()> {
+        self.check_alive()?;
+        let var = signal.var_name( &self.tk() )?;
+        self.tk().run(( self.path, "configure", "-variable", var, "-onvalue", 1, "-offvalue", 0 ))?;
+        self.hold_signal( "-variable", signal );
+        Ok(())
+    }
+
+    /// Keeps the option named by `_name_fn`, e.g. `background`, in sync with `signal`. The
+    /// binding is one-way, and ends when this widget is destroyed or the option is bound to
+    /// another signal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    /// use tk::state::Store;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let store = Store::new( &tk );
+    ///     let label = tk.root().add_label(())?;
+    ///
+    ///     let first = store.signal( String::from( "red" ));
+    ///     let second = store.signal( String::from( "blue" ));
+    ///     label.bind_option( background, &first )?;
+    ///     label.bind_option( background, &second )?;
+    ///
+    ///     first.set( String::from( "green" ));
+    ///     store.flush();
+    ///     assert_eq!( label.cget( background )?.to_string(), "blue" );
+    ///
+    ///     second.set( String::from( "yellow" ));
+    ///     store.flush();
+    ///     assert_eq!( label.cget( background )?.to_string(), "yellow" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn bind_option<Opt,T>( &self, _name_fn: fn(Obj)->Opt, signal: &Signal<T> ) -> InterpResult<()>
+        where Opt: TkOption
+            , T  : Clone + Into<Obj> + 'static
+    {
+        self.check_alive()?;
+        let configure = |widget: &Widget<Inst>, value: &T| -> InterpResult<()> {
+            widget.tk().run(( widget.path, "configure", <Opt as TkOption>::NAME, value.clone() ))
+        };
+        signal.with( |value| configure( self, value ))?;
+
+        let token = Rc::downgrade( &self.hold_signal( <Opt as TkOption>::NAME, signal ));
+        let widget = *self;
+        signal.observe( move |value| token.upgrade().is_some_and( |token| token.get() )
+            && widget.is_alive()
+            && configure( &widget, value ).is_ok() );
+        Ok(())
+    }
+
+    fn bind_var<T>( &self, option: &'static str, signal: &Signal<T> ) -> InterpResult<()>
+        where T: Clone + Into<Obj> + TryFrom<Obj> + 'static
+    {
+        self.check_alive()?;
+        let var = signal.var_name( &self.tk() )?;
+        self.tk().run(( self.path, "configure", option, var ))?;
+        self.hold_signal( option, signal );
+        Ok(())
+    }
+
+    // Owns a command returning the value of `signal`, which holds the signal and the token of
+    // the binding until the command is deleted. The token of the previous binding of `option`
+    // is revoked at once.
+    fn hold_signal<T>( &self, option: &str, signal: &Signal<T> ) -> BindingToken
+        where T: Clone + Into<Obj> + 'static
+    {
+        let token = Rc::new( Cell::new( true ));
+        BINDINGS.with( |bindings| {
+            let mut bindings = bindings.borrow_mut();
+            bindings.retain( |_, token| token.strong_count() > 0 );
+            let previous = bindings.insert(( self.path, option.to_owned() ), Rc::downgrade( &token ));
+            if let Some( previous ) = previous.and_then( |previous| previous.upgrade() ) {
+                previous.set( false );
+            }
+        });
+
+        let held = ( signal.clone(), token.clone() );
+        let command = tclosure!( self.tk(), move || -> TkResult<Obj> {
+            let ( signal, _token ) = &held;
+            Ok( signal.get().into() )
+        });
+        self.own_keyed_command( format!( "signal {option}" ), &command );
+        token
+    }
+}
@@ -27,6 +27,7 @@ use crate::{
         TkEmbededImageOpt,
         TkEmbededWindowOpt,
         TkTextTagOpt,
+        option_value,
     },
     range::TkRange,
     traits::Delete,
@@ -597,11 +598,13 @@ impl<Inst:TkInstance> self::TkText<Inst> {
                 .collect::<Vec<_>>() )
     }
 
-    pub fn image_cget<Opt>( &self, index: Index, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn image_cget<Opt>( &self, index: Index, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkEmbededImageOpt>
     {
-        self.0.tk().eval(( self.0.path, "image", "cget", index, <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "image", "cget", index, <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn image_configure<Opts>( &self, index: Index, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -835,11 +838,13 @@ impl<Inst:TkInstance> self::TkText<Inst> {
         self.tk().run(( self.path, "tag", "bind", tag_name, sequence.into(), script.into() ))
     }
 
-    pub fn tag_cget<Opt>( &self, tag_name: &str, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn tag_cget<Opt>( &self, tag_name: &str, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkTextTagOpt>
     {
-        self.0.tk().eval(( self.0.path, "tag", "cget", tag_name, <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "tag", "cget", tag_name, <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn tag_configure<Opts>( &self, tag_name: &str, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
@@ -1007,11 +1012,13 @@ impl<Inst:TkInstance> self::TkText<Inst> {
         self.0.tk().run(( self.0.path, "tag", "remove", tag_name, index ))
     }
 
-    pub fn window_cget<Opt>( &self, window_name: &str, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn window_cget<Opt>( &self, window_name: &str, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TkEmbededWindowOpt>
     {
-        self.0.tk().eval(( self.0.path, "window", "cget", window_name, <Opt as TkOption>::NAME ))
+        let obj = self.0.tk().eval(( self.0.path, "window", "cget", window_name, <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn window_configure<Opts>( &self, window_name: &str, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<()>
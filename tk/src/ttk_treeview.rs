@@ -22,6 +22,7 @@ use crate::{
         TtkTreeviewHeadingOpt,
         TtkTreeviewItemOpt,
         TtkTreeviewTagOpt,
+        option_value,
     },
     range::{
         TkDefaultStart,
@@ -165,11 +166,13 @@ impl<TK:TkInstance> TtkTreeview<TK> {
                 .collect::<Vec<_>>() )
     }
 
-    pub fn column<Opt>( &self, column: impl Into<Column>, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn column<Opt>( &self, column: impl Into<Column>, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TtkTreeviewColumnOpt>
     {
-        self.tk().eval(( self.path, "column", column.into(), <Opt as TkOption>::NAME ))
+        let obj = self.tk().eval(( self.path, "column", column.into(), <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn set_column<Opts>( &self, column: impl Into<Column>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Obj>
@@ -211,11 +214,13 @@ impl<TK:TkInstance> TtkTreeview<TK> {
         }
     }
 
-    pub fn heading<Opt>( &self, column: impl Into<Column>, _name_fn: fn(Obj)->Opt ) -> InterpResult<Obj>
+    #[cex]
+    pub fn heading<Opt>( &self, column: impl Into<Column>, _name_fn: fn(Obj)->Opt ) -> Result!( <Opt as TkOption>::Value throws DeError, InterpError )
         where Opt : TkOption
                   + Into<TtkTreeviewHeadingOpt>
     {
-        self.tk().eval(( self.path, "heading", column.into(), <Opt as TkOption>::NAME ))
+        let obj = self.tk().eval(( self.path, "heading", column.into(), <Opt as TkOption>::NAME ))?;
+        ret!( option_value::<Opt>( obj ))
     }
 
     pub fn set_heading<Opts>( &self, column: impl Into<Column>, opts: impl Into<PathOptsWidgets<Opts,()>> ) -> InterpResult<Obj>
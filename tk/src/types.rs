@@ -1,6 +1,12 @@
-use tcl::Obj;
+use bitflags::bitflags;
+
+use tcl::{
+    Obj,
+    error::{DeError, DeKind},
+};
 
 use crate::{
+    opt::TkOptionValue,
     error::{
         TkGeometryParseError,
         TkScreenNameParseError,
@@ -64,6 +70,26 @@ impl From<TkDistance> for Obj {
     }
 }
 
+/// Parses e.g. "2c" or "1.5i". A plain number is `Pixels`, which is a count of characters or
+/// lines for options measured so, e.g. the "-width" of a button showing text.
+impl TkOptionValue for TkDistance {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> {
+        let text = obj.to_string();
+        let text = text.trim();
+        let ( number, unit ): ( &str, fn( c_double ) -> TkDistance ) = match text.chars().last() {
+            Some( 'c' ) => ( &text[ ..text.len()-1 ], TkDistance::Centimeters   ),
+            Some( 'i' ) => ( &text[ ..text.len()-1 ], TkDistance::Inches        ),
+            Some( 'm' ) => ( &text[ ..text.len()-1 ], TkDistance::Millimeters   ),
+            Some( 'p' ) => ( &text[ ..text.len()-1 ], TkDistance::PrinterPoints ),
+            _           => ( text,                    TkDistance::Pixels        ),
+        };
+        match number.parse::<c_double>() {
+            Ok( number ) => Ok( unit( number )),
+            Err( _ ) => Err( DeError::new( DeKind::NotF64, obj )),
+        }
+    }
+}
+
 #[derive( Copy, Clone, Default, Debug, PartialEq, Eq )]
 pub struct TkBBox {
     pub x : c_int,
@@ -250,6 +276,29 @@ impl From<TkState> for Obj {
     }
 }
 
+/// The value of the "-state" option of widgets and canvas items.
+#[derive( Copy, Clone, Debug, PartialEq, Eq )]
+#[derive( serde::Serialize, serde::Deserialize )]
+pub enum TkWidgetState {
+    #[serde( rename = "normal"   )] Normal,
+    #[serde( rename = "active"   )] Active,
+    #[serde( rename = "disabled" )] Disabled,
+    #[serde( rename = "hidden"   )] Hidden,
+    #[serde( rename = "readonly" )] Readonly,
+}
+
+impl From<TkWidgetState> for Obj {
+    fn from( state: TkWidgetState ) -> Obj {
+        match state {
+            TkWidgetState::Normal   => "normal"  .into(),
+            TkWidgetState::Active   => "active"  .into(),
+            TkWidgetState::Disabled => "disabled".into(),
+            TkWidgetState::Hidden   => "hidden"  .into(),
+            TkWidgetState::Readonly => "readonly".into(),
+        }
+    }
+}
+
 #[derive( Copy, Clone, Debug, PartialEq, Eq )]
 #[derive( serde::Serialize, serde::Deserialize )]
 pub enum TkVisualClass {
@@ -317,3 +366,263 @@ pub enum TtkTreeviewRegion {
     #[serde( rename = "tree"      )] Tree,
     #[serde( rename = "cell"      )] Cell,
 }
+
+// Defines enums of option values, convertible to and from the names used by Tk.
+macro_rules! def_option_value_enums {
+    ($( $(#[$meta:meta])* $ty:ident { $( $(#[$variant_meta:meta])* $variant:ident => $name:expr, )+ } )+) => {$(
+        $(#[$meta])*
+        ///
+        #[doc = concat!( "Not to be confused with the option `tk::opt::", stringify!( $ty ), "`, which shares its name." )]
+        #[derive( Copy, Clone, Debug, PartialEq, Eq, Hash )]
+        pub enum $ty {
+            $( $(#[$variant_meta])* $variant, )+
+        }
+
+        impl $ty {
+            /// The name used by Tk.
+            pub fn as_str( &self ) -> &'static str {
+                match self {
+                    $( $ty::$variant => $name, )+
+                }
+            }
+        }
+
+        impl Display for $ty {
+            fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+                f.write_str( self.as_str() )
+            }
+        }
+
+        impl From<$ty> for Obj {
+            fn from( value: $ty ) -> Obj { value.as_str().into() }
+        }
+
+        impl TkOptionValue for $ty {
+            fn from_option_value( obj: Obj ) -> Result<Self, DeError> {
+                match obj.to_string().as_str() {
+                    $( $name => Ok( $ty::$variant ), )+
+                    _ => Err( DeError::new( DeKind::NotEnum, obj )),
+                }
+            }
+        }
+    )+};
+}
+
+def_option_value_enums! {
+    /// The value of "-anchor".
+    TkAnchor {
+        N      => "n",
+        NE     => "ne",
+        E      => "e",
+        SE     => "se",
+        S      => "s",
+        SW     => "sw",
+        W      => "w",
+        NW     => "nw",
+        Center => "center",
+    }
+
+    /// The value of "-compound", placing the image relative to the text.
+    TkCompound {
+        None   => "none",
+        Bottom => "bottom",
+        Top    => "top",
+        Left   => "left",
+        Right  => "right",
+        Center => "center",
+        /// Ttk widgets only: displays the text only.
+        Text   => "text",
+        /// Ttk widgets only: displays the image only.
+        Image  => "image",
+    }
+
+    /// The value of "-fill" of the packer, e.g. `pack( -fill( TkFill::X ))`. The "-fill" of canvas
+    /// items is a color instead, so `cget()` and its kin return it as `Obj`.
+    TkFill {
+        None => "none",
+        X    => "x",
+        Y    => "y",
+        Both => "both",
+    }
+
+    /// The value of "-justify".
+    TkJustify {
+        Left   => "left",
+        Center => "center",
+        Right  => "right",
+    }
+
+    /// The value of "-orient".
+    TkOrient {
+        Horizontal => "horizontal",
+        Vertical   => "vertical",
+    }
+
+    /// The value of "-relief".
+    TkRelief {
+        Flat   => "flat",
+        Groove => "groove",
+        Raised => "raised",
+        Ridge  => "ridge",
+        Solid  => "solid",
+        Sunken => "sunken",
+    }
+
+    /// The value of "-wrap" of text widgets.
+    TkWrap {
+        None => "none",
+        Char => "char",
+        Word => "word",
+    }
+
+    /// The value of "-cursor": the cursors available on all platforms, from the X cursor font.
+    TkCursor {
+        XCursor          => "X_cursor",
+        Arrow            => "arrow",
+        BasedArrowDown   => "based_arrow_down",
+        BasedArrowUp     => "based_arrow_up",
+        Boat             => "boat",
+        Bogosity         => "bogosity",
+        BottomLeftCorner => "bottom_left_corner",
+        BottomRightCorner=> "bottom_right_corner",
+        BottomSide       => "bottom_side",
+        BottomTee        => "bottom_tee",
+        BoxSpiral        => "box_spiral",
+        CenterPtr        => "center_ptr",
+        Circle           => "circle",
+        Clock            => "clock",
+        CoffeeMug        => "coffee_mug",
+        Cross            => "cross",
+        CrossReverse     => "cross_reverse",
+        Crosshair        => "crosshair",
+        DiamondCross     => "diamond_cross",
+        Dot              => "dot",
+        Dotbox           => "dotbox",
+        DoubleArrow      => "double_arrow",
+        DraftLarge       => "draft_large",
+        DraftSmall       => "draft_small",
+        DrapedBox        => "draped_box",
+        Exchange         => "exchange",
+        Fleur            => "fleur",
+        Gobbler          => "gobbler",
+        Gumby            => "gumby",
+        Hand1            => "hand1",
+        Hand2            => "hand2",
+        Heart            => "heart",
+        Icon             => "icon",
+        IronCross        => "iron_cross",
+        LeftPtr          => "left_ptr",
+        LeftSide         => "left_side",
+        LeftTee          => "left_tee",
+        Leftbutton       => "leftbutton",
+        LlAngle          => "ll_angle",
+        LrAngle          => "lr_angle",
+        Man              => "man",
+        Middlebutton     => "middlebutton",
+        Mouse            => "mouse",
+        None             => "none",
+        Pencil           => "pencil",
+        Pirate           => "pirate",
+        Plus             => "plus",
+        QuestionArrow    => "question_arrow",
+        RightPtr         => "right_ptr",
+        RightSide        => "right_side",
+        RightTee         => "right_tee",
+        Rightbutton      => "rightbutton",
+        RtlLogo          => "rtl_logo",
+        Sailboat         => "sailboat",
+        SbDownArrow      => "sb_down_arrow",
+        SbHDoubleArrow   => "sb_h_double_arrow",
+        SbLeftArrow      => "sb_left_arrow",
+        SbRightArrow     => "sb_right_arrow",
+        SbUpArrow        => "sb_up_arrow",
+        SbVDoubleArrow   => "sb_v_double_arrow",
+        Shuttle          => "shuttle",
+        Sizing           => "sizing",
+        Spider           => "spider",
+        Spraycan         => "spraycan",
+        Star             => "star",
+        Target           => "target",
+        Tcross           => "tcross",
+        TopLeftArrow     => "top_left_arrow",
+        TopLeftCorner    => "top_left_corner",
+        TopRightCorner   => "top_right_corner",
+        TopSide          => "top_side",
+        TopTee           => "top_tee",
+        Trek             => "trek",
+        UlAngle          => "ul_angle",
+        Umbrella         => "umbrella",
+        UrAngle          => "ur_angle",
+        Watch            => "watch",
+        Xterm            => "xterm",
+    }
+}
+
+bitflags! {
+    /// The value of "-sticky", e.g. `TkSticky::N | TkSticky::EW`. Not to be confused with the
+    /// option `tk::opt::TkSticky`, which shares its name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tcl::*;
+    /// use tk::*;
+    /// use tk::cmd::*;
+    ///
+    /// fn main() -> TkResult<()> {
+    ///     let tk = make_tk!()?;
+    ///     let label = tk.root()
+    ///         .add_label( -text("typed") -relief( TkRelief::Sunken ) -anchor( TkAnchor::NW ) -cursor( TkCursor::Hand2 ))?
+    ///         .grid( -sticky( TkSticky::N | TkSticky::EW ))?;
+    ///     let frame = tk.root().add_frame(())?.grid( -row(1) )?;
+    ///     frame.add_label( -text("packed") )?.pack( -fill( TkFill::X ))?;
+    ///
+    ///     assert_eq!( label.cget( relief )?, Some( TkRelief::Sunken ));
+    ///     assert_eq!( label.cget( anchor )?, Some( TkAnchor::NW ));
+    ///     assert_eq!( label.cget( text )?, "typed" );
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub struct TkSticky: u8 {
+        const N    = 0b0001;
+        const E    = 0b0010;
+        const S    = 0b0100;
+        const W    = 0b1000;
+        const NS   = Self::N.bits | Self::S.bits;
+        const EW   = Self::E.bits | Self::W.bits;
+        const NSEW = Self::NS.bits | Self::EW.bits;
+    }
+}
+
+impl Display for TkSticky {
+    fn fmt( &self, f: &mut fmt::Formatter ) -> fmt::Result {
+        for ( side, name ) in [( TkSticky::N, 'n' ), ( TkSticky::E, 'e' ), ( TkSticky::S, 's' ), ( TkSticky::W, 'w' )] {
+            if self.contains( side ) {
+                write!( f, "{}", name )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<TkSticky> for Obj {
+    fn from( sticky: TkSticky ) -> Obj { sticky.to_string().into() }
+}
+
+impl TkOptionValue for TkSticky {
+    fn from_option_value( obj: Obj ) -> Result<Self, DeError> {
+        let mut sticky = TkSticky::empty();
+        for ch in obj.to_string().chars() {
+            match ch {
+                'n' => sticky |= TkSticky::N,
+                'e' => sticky |= TkSticky::E,
+                's' => sticky |= TkSticky::S,
+                'w' => sticky |= TkSticky::W,
+                ',' | ' ' => (),
+                _ => return Err( DeError::new( DeKind::NotEnum, obj )),
+            }
+        }
+        Ok( sticky )
+    }
+}
@@ -4,7 +4,6 @@ use cex::*;
 
 use crate::{
     InterpResult,
-    NOT_SEND_SYNC,
     Tk,
     TkColor,
     TkCoord,
@@ -288,7 +287,7 @@ impl<Inst:TkInstance> Widget<Inst> {
     pub fn winfo_toplevel( &self ) -> InterpResult<TkToplevel<Inst>> {
         self.tk()
             .eval(( "winfo", "toplevel", self.path ))
-            .map( |obj| TkToplevel( Widget{ path: Tk::<Inst>::make_or_get_path( &obj.to_string() ), inst: self.inst, mark: NOT_SEND_SYNC }))
+            .map( |obj| TkToplevel( Widget::from_tracked_path( &self.tk(), &obj.to_string() )))
     }
 
     pub fn winfo_viewable( &self ) -> InterpResult<bool> {
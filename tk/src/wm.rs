@@ -3,8 +3,6 @@ use enumx::predefined::*;
 use cex::*;
 
 use crate::{
-    NOT_SEND_SYNC,
-    Tk,
     TkCoord,
     TkGeometry,
     TkHandler,
@@ -487,7 +485,7 @@ impl<Inst:TkInstance> Widget<Inst> {
         if path.is_empty() {
             Ok( None )
         } else {
-            Ok( Some( TkToplevel( Widget{ path: Tk::<Inst>::make_or_get_path( &path ), inst: self.inst, mark: NOT_SEND_SYNC })))
+            Ok( Some( TkToplevel( Widget::from_tracked_path( &self.tk(), &path ))))
         }
     }
 